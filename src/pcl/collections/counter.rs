@@ -20,7 +20,7 @@ use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::iter::FromIterator;
-use std::ops::{Deref, Index};
+use std::ops::{AddAssign, Deref, Index};
 
 /// 与えられた配列の中に特定の要素が何個あるかをカウントするコレクション。
 pub struct Counter<T> {
@@ -54,6 +54,108 @@ impl<T: Eq + Hash> Counter<T> {
         static ZERO: usize = 0;
         self.inner.get(index).unwrap_or(&ZERO)
     }
+
+    /// 要素 `item` の個数を 1 増やす。
+    pub fn incr(&mut self, item: T) {
+        *self.inner.entry(item).or_insert(0) += 1;
+    }
+
+    /// 要素 `item` の個数を 1 減らす。個数は 0 未満にはならず、0 になった要素は取り除く。
+    pub fn decr<Q>(&mut self, item: &Q)
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let remove = match self.inner.get_mut(item) {
+            Some(cnt) if *cnt > 1 => {
+                *cnt -= 1;
+                false
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if remove {
+            self.inner.remove(item);
+        }
+    }
+
+    /// すべての要素の個数の合計を返す。
+    pub fn total(&self) -> usize {
+        self.inner.values().sum()
+    }
+
+    /// 要素とその個数の組を順不同で列挙する。個数が 0 の要素は現れない。
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.inner.iter().map(|(x, &cnt)| (x, cnt))
+    }
+
+    /// 個数の多い順に、上位 `n` 個の要素とその個数を返す。
+    ///
+    /// 個数が同じ要素は小さい順に並べる。`n` が要素の種類数以上であれば、すべての要素を返す。
+    ///
+    /// ```
+    /// # use procon_lib::pcl::collections::counter::Counter;
+    /// // use crate::pcl::collections::counter::Counter;
+    /// let counter: Counter<_> = "abracadabra".chars().collect();
+    /// assert_eq!(counter.most_common(2), vec![(&'a', 5), (&'b', 2)]);
+    /// ```
+    ///
+    /// # 計算量
+    ///
+    /// O(k log k) (k は要素の種類数)
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)>
+    where
+        T: Ord,
+    {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        entries.truncate(n);
+        entries
+    }
+}
+
+impl<T: Eq + Hash + Clone> Counter<T> {
+    /// `other` の各要素の個数を加える。
+    ///
+    /// ```
+    /// # use procon_lib::pcl::collections::counter::Counter;
+    /// // use crate::pcl::collections::counter::Counter;
+    /// let mut a: Counter<_> = "aab".chars().collect();
+    /// let b: Counter<_> = "bbc".chars().collect();
+    /// a.add_counts(&b);
+    /// assert_eq!((a[&'a'], a[&'b'], a[&'c']), (2, 3, 1));
+    /// a.sub_counts(&b);
+    /// a.sub_counts(&b);
+    /// assert_eq!((a[&'a'], a[&'b'], a[&'c']), (2, 0, 0));
+    /// ```
+    pub fn add_counts(&mut self, other: &Counter<T>) {
+        for (x, cnt) in other.iter() {
+            *self.inner.entry(x.clone()).or_insert(0) += cnt;
+        }
+    }
+
+    /// `other` の各要素の個数を引く。個数は 0 未満にはならず、0 になった要素は取り除く。
+    pub fn sub_counts(&mut self, other: &Counter<T>) {
+        for (x, cnt) in other.iter() {
+            let remove = match self.inner.get_mut(x) {
+                Some(c) if *c > cnt => {
+                    *c -= cnt;
+                    false
+                }
+                Some(_) => true,
+                None => false,
+            };
+            if remove {
+                self.inner.remove(x);
+            }
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> AddAssign<&Counter<T>> for Counter<T> {
+    fn add_assign(&mut self, other: &Counter<T>) {
+        self.add_counts(other);
+    }
 }
 
 impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
@@ -79,9 +181,62 @@ where
     }
 }
 
+/// 数列 `a` の長さ `k` の各区間 (窓) に含まれる要素の種類数を求める。
+///
+/// 戻り値の `i` 番目の要素は `a[i..i + k]` に含まれる要素の種類数。`k > a.len()` のときは空の
+/// `Vec` を返す。`k` は正でなければならない。
+///
+/// 窓を一つずつずらしながら各要素の個数を `HashMap` で管理するので、窓ごとに数え直す必要はない。
+///
+/// ```
+/// # use procon_lib::pcl::collections::counter::distinct_counts_in_windows;
+/// // use crate::pcl::collections::counter::distinct_counts_in_windows;
+/// let a = [1, 2, 1, 3, 3, 3];
+/// assert_eq!(distinct_counts_in_windows(&a, 3), vec![2, 3, 2, 1]);
+/// ```
+///
+/// # 計算量
+///
+/// O(n)
+pub fn distinct_counts_in_windows<T: Eq + Hash + Clone>(a: &[T], k: usize) -> Vec<usize> {
+    assert!(k > 0, "window size must be positive");
+    if k > a.len() {
+        return Vec::new();
+    }
+
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    for x in &a[..k] {
+        *counts.entry(x.clone()).or_insert(0) += 1;
+    }
+
+    let mut res = Vec::with_capacity(a.len() - k + 1);
+    res.push(counts.len());
+    for i in k..a.len() {
+        *counts.entry(a[i].clone()).or_insert(0) += 1;
+
+        // 窓から外れる要素を取り除く。個数が 0 になったら種類数に数えないよう削除する。
+        let out = &a[i - k];
+        let remove = {
+            let cnt = counts
+                .get_mut(out)
+                .expect("the element must be in the window");
+            *cnt -= 1;
+            *cnt == 0
+        };
+        if remove {
+            counts.remove(out);
+        }
+
+        res.push(counts.len());
+    }
+
+    res
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pcl::test_util::XorShift;
 
     #[test]
     fn counter_slice() {
@@ -144,4 +299,111 @@ mod tests {
         assert_eq!(c[&"world".to_string()], 1);
         assert_eq!(c[&"rust".to_string()], 0);
     }
+
+    #[test]
+    fn counter_total_and_iter() {
+        let v = vec![1, 2, 3, 3, 6, 4, 5, 2];
+        let c = Counter::from_slice(&v);
+        assert_eq!(c.total(), v.len());
+
+        let mut entries: Vec<_> = c.iter().map(|(&&x, cnt)| (x, cnt)).collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![(1, 1), (2, 2), (3, 2), (4, 1), (5, 1), (6, 1)]
+        );
+
+        let c = Counter::<String>::from_iter(vec![]);
+        assert_eq!(c.total(), 0);
+        assert_eq!(c.iter().count(), 0);
+    }
+
+    #[test]
+    fn counter_add_and_sub() {
+        let mut a = Counter::from_iter(vec![1, 1, 2, 3]);
+        let b = Counter::from_iter(vec![2, 3, 3, 4]);
+        a += &b;
+        assert_eq!((a[&1], a[&2], a[&3], a[&4]), (2, 2, 3, 1));
+        assert_eq!(a.total(), 8);
+
+        let mut c = Counter::from_iter(vec![1, 2, 2]);
+        c.add_counts(&Counter::from_iter(vec![]));
+        assert_eq!(c.total(), 3);
+
+        // 引き過ぎても 0 で止まり、0 になった要素は残らない。
+        a.sub_counts(&b);
+        a.sub_counts(&b);
+        assert_eq!((a[&1], a[&2], a[&3], a[&4]), (2, 0, 0, 0));
+        assert_eq!(a.iter().count(), 1);
+        a.sub_counts(&Counter::from_iter(vec![1, 5]));
+        assert_eq!(a[&1], 1);
+        assert_eq!(a[&5], 0);
+        assert_eq!(a.total(), 1);
+    }
+
+    #[test]
+    fn counter_incr_and_decr() {
+        let mut c = Counter::from_iter(vec!["x".to_string()]);
+        c.incr("y".to_string());
+        c.incr("x".to_string());
+        assert_eq!((c["x"], c["y"]), (2, 1));
+
+        // 0 になった要素は取り除かれる。
+        c.decr("y");
+        assert_eq!(c.get("y"), &0);
+        assert_eq!(c.iter().count(), 1);
+
+        // 0 未満にはならない。
+        c.decr("y");
+        c.decr("z");
+        assert_eq!((c["y"], c["z"]), (0, 0));
+        c.decr("x");
+        assert_eq!(c["x"], 1);
+        assert_eq!(c.total(), 1);
+    }
+
+    #[test]
+    fn counter_most_common() {
+        let v = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let c = Counter::from_slice(&v);
+        assert_eq!(c.most_common(1), vec![(&&5, 3)]);
+        assert_eq!(c.most_common(3), vec![(&&5, 3), (&&1, 2), (&&3, 2)]);
+
+        // n が種類数以上ならすべて返す。
+        let all = c.most_common(100);
+        assert_eq!(all.len(), 7);
+        assert_eq!(c.most_common(7), all);
+        assert_eq!(
+            all[3..]
+                .iter()
+                .map(|&(&&x, cnt)| (x, cnt))
+                .collect::<Vec<_>>(),
+            vec![(2, 1), (4, 1), (6, 1), (9, 1)]
+        );
+        assert!(c.most_common(0).is_empty());
+        assert!(Counter::<i32>::from_iter(vec![]).most_common(3).is_empty());
+    }
+
+    #[test]
+    fn distinct_counts_in_windows_matches_naive() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for n in 0..30 {
+            let a: Vec<u64> = (0..n).map(|_| rng.next_u64() % 5).collect();
+            for k in 1..=n + 1 {
+                let expected: Vec<usize> = if k > n {
+                    Vec::new()
+                } else {
+                    a.windows(k)
+                        .map(|w| Counter::from_slice(w).inner.len())
+                        .collect()
+                };
+                assert_eq!(distinct_counts_in_windows(&a, k), expected);
+            }
+        }
+
+        let words = ["a", "b", "a", "c"];
+        let words: Vec<String> = words.iter().map(|s| s.to_string()).collect();
+        assert_eq!(distinct_counts_in_windows(&words, 2), vec![2, 2, 2]);
+    }
 }
@@ -0,0 +1,184 @@
+//! 実行ごとに異なるシードを持つハッシュ関数 `FastHasher` と、それを使う `FastHashMap` /
+//! `FastHashSet` を定義する。
+//!
+//! 標準の `HashMap` の既定のハッシュ関数は安全だが遅く、一方で固定のハッシュ関数を使うと、衝突する
+//! キーを狙って作られた入力 (anti-hash テスト) で O(n) の衝突が起きてしまう。ここでは splitmix64 の
+//! 混合関数を、起動時刻などから作ったシードと組み合わせて使う。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::collections::fast_hash::{FastHashMap, FastHashSet};
+//! // use crate::pcl::collections::fast_hash::{FastHashMap, FastHashSet};
+//! let mut map = FastHashMap::default();
+//! *map.entry("apple").or_insert(0) += 3;
+//! *map.entry("apple").or_insert(0) += 2;
+//! assert_eq!(map["apple"], 5);
+//!
+//! let set: FastHashSet<_> = [3, 1, 4, 1, 5].iter().copied().collect();
+//! assert_eq!(set.len(), 4);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `FastHashBuilder` を使う `HashMap` 。
+pub type FastHashMap<K, V> = HashMap<K, V, FastHashBuilder>;
+
+/// `FastHashBuilder` を使う `HashSet` 。
+pub type FastHashSet<T> = HashSet<T, FastHashBuilder>;
+
+/// splitmix64 の混合関数。
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// シード付きの高速なハッシュ関数。
+#[derive(Debug, Clone, Copy)]
+pub struct FastHasher {
+    state: u64,
+}
+
+impl Hasher for FastHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let word = chunk
+                .iter()
+                .rev()
+                .fold(0u64, |acc, &b| acc << 8 | u64::from(b));
+            self.write_u64(word);
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.state = mix(self.state ^ i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        mix(self.state)
+    }
+}
+
+/// `FastHasher` を生成する `BuildHasher` 。
+///
+/// `Default` で生成すると、現在時刻とスタック上のアドレスからシードを決める。再現性が必要なら
+/// `with_seed()` でシードを指定する。
+#[derive(Debug, Clone, Copy)]
+pub struct FastHashBuilder {
+    seed: u64,
+}
+
+impl FastHashBuilder {
+    /// 指定したシードで生成する。
+    pub fn with_seed(seed: u64) -> FastHashBuilder {
+        FastHashBuilder { seed: mix(seed) }
+    }
+}
+
+impl Default for FastHashBuilder {
+    fn default() -> FastHashBuilder {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() ^ u64::from(d.subsec_nanos()) << 32)
+            .unwrap_or(0);
+        let local = 0u8;
+        let addr = &local as *const u8 as u64;
+        FastHashBuilder::with_seed(nanos ^ mix(addr))
+    }
+}
+
+impl BuildHasher for FastHashBuilder {
+    type Hasher = FastHasher;
+
+    fn build_hasher(&self) -> FastHasher {
+        FastHasher { state: self.seed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+    use std::collections::{HashMap, HashSet};
+    use std::hash::Hash;
+
+    // `BuildHasher::hash_one()` は古い Rust では使えない。
+    #[allow(unknown_lints, clippy::manual_hash_one)]
+    fn hash_of<T: Hash>(builder: &FastHashBuilder, value: &T) -> u64 {
+        let mut hasher = builder.build_hasher();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn fast_hash_map_behaves_like_std() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        let mut fast = FastHashMap::default();
+        let mut std = HashMap::new();
+
+        for _ in 0..5000 {
+            let state = rng.next_u64();
+            let key = state % 300;
+            match state >> 60 & 3 {
+                0 => assert_eq!(fast.remove(&key), std.remove(&key)),
+                1 => assert_eq!(fast.get(&key), std.get(&key)),
+                _ => {
+                    *fast.entry(key).or_insert(0) += state >> 62;
+                    *std.entry(key).or_insert(0) += state >> 62;
+                }
+            }
+            assert_eq!(fast.len(), std.len());
+        }
+
+        let mut fast: Vec<_> = fast.into_iter().collect();
+        let mut std: Vec<_> = std.into_iter().collect();
+        fast.sort_unstable();
+        std.sort_unstable();
+        assert_eq!(fast, std);
+    }
+
+    #[test]
+    fn fast_hash_set_behaves_like_std() {
+        let words = [
+            "abc",
+            "",
+            "abd",
+            "abc",
+            "a long string over eight bytes",
+            "",
+        ];
+        let fast: FastHashSet<_> = words.iter().collect();
+        let std: HashSet<_> = words.iter().collect();
+        assert_eq!(fast.len(), std.len());
+        assert!(words.iter().all(|w| fast.contains(w)));
+        assert!(!fast.contains(&"abe"));
+
+        let mut map = FastHashMap::with_hasher(FastHashBuilder::with_seed(42));
+        map.insert((1, 'x'), "one");
+        map.insert((2, 'y'), "two");
+        assert_eq!(map.get(&(1, 'x')), Some(&"one"));
+        assert_eq!(map.get(&(1, 'y')), None);
+    }
+
+    #[test]
+    fn fast_hash_seed() {
+        // 同じシードなら同じハッシュ値、異なるシードなら (ほぼ確実に) 異なるハッシュ値になる。
+        let a = FastHashBuilder::with_seed(1);
+        let b = FastHashBuilder::with_seed(2);
+        assert_eq!(
+            hash_of(&a, &"hello"),
+            hash_of(&FastHashBuilder::with_seed(1), &"hello")
+        );
+        assert_ne!(hash_of(&a, &"hello"), hash_of(&b, &"hello"));
+        assert_ne!(hash_of(&a, &12_345u64), hash_of(&b, &12_345u64));
+        assert_ne!(hash_of(&a, &1u64), hash_of(&a, &2u64));
+    }
+}
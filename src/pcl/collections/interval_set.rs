@@ -0,0 +1,202 @@
+//! 整数の半開区間の和集合を管理する `IntervalSet` を定義する。
+//!
+//! 内部では区間の左端をキー、右端を値とする `BTreeMap` を利用している。重なっていたり隣接していた
+//! りする区間は常に一つにまとめられる。
+//!
+//! # Example
+//!
+//! ```
+//! # use procon_lib::pcl::collections::interval_set::IntervalSet;
+//! #
+//! let mut set = IntervalSet::new();
+//! set.insert(0, 3);
+//! set.insert(5, 7);
+//! assert!(set.contains(2));
+//! assert!(!set.contains(3));
+//! assert_eq!(set.mex(), 3);
+//!
+//! set.insert(3, 5);
+//! assert_eq!(set.intervals(), vec![(0, 7)]);
+//! assert_eq!(set.mex(), 7);
+//! ```
+
+use std::collections::BTreeMap;
+
+/// 整数の半開区間の和集合。
+#[derive(Debug, Clone, Default)]
+pub struct IntervalSet {
+    inner: BTreeMap<i64, i64>,
+}
+
+impl IntervalSet {
+    /// 空の集合を生成する。
+    pub fn new() -> IntervalSet {
+        IntervalSet {
+            inner: BTreeMap::new(),
+        }
+    }
+
+    /// 区間 `[l, r)` を追加する。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(log n)
+    pub fn insert(&mut self, mut l: i64, mut r: i64) {
+        if l >= r {
+            return;
+        }
+
+        // 左側で重なっているか隣接している区間を取り込む。
+        if let Some((&a, &b)) = self.inner.range(..=l).next_back() {
+            if b >= l {
+                l = a;
+                r = r.max(b);
+                self.inner.remove(&a);
+            }
+        }
+
+        // 左端が [l, r] に含まれる区間を取り込む。
+        let covered: Vec<_> = self.inner.range(l..=r).map(|(&a, &b)| (a, b)).collect();
+        for (a, b) in covered {
+            r = r.max(b);
+            self.inner.remove(&a);
+        }
+
+        self.inner.insert(l, r);
+    }
+
+    /// 区間 `[l, r)` を取り除く。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(log n)
+    pub fn remove(&mut self, l: i64, r: i64) {
+        if l >= r {
+            return;
+        }
+
+        // 左側からはみ出している区間を切り詰める。
+        if let Some((&a, &b)) = self.inner.range(..l).next_back() {
+            if b > l {
+                self.inner.insert(a, l);
+                if b > r {
+                    self.inner.insert(r, b);
+                }
+            }
+        }
+
+        let covered: Vec<_> = self.inner.range(l..r).map(|(&a, &b)| (a, b)).collect();
+        for (a, b) in covered {
+            self.inner.remove(&a);
+            if b > r {
+                self.inner.insert(r, b);
+            }
+        }
+    }
+
+    /// `x` がいずれかの区間に含まれているかどうかを返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn contains(&self, x: i64) -> bool {
+        match self.inner.range(..=x).next_back() {
+            Some((_, &b)) => x < b,
+            None => false,
+        }
+    }
+
+    /// どの区間にも含まれない最小の非負整数を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn mex(&self) -> i64 {
+        // 区間はまとめられているので、0 を含む区間の右端は含まれていない。
+        match self.inner.range(..=0).next_back() {
+            Some((_, &b)) if b > 0 => b,
+            _ => 0,
+        }
+    }
+
+    /// 区間を左から順に並べたリストを返す。
+    pub fn intervals(&self) -> Vec<(i64, i64)> {
+        self.inner.iter().map(|(&a, &b)| (a, b)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+
+    #[test]
+    fn interval_set_insert_remove() {
+        let mut set = IntervalSet::new();
+        set.insert(1, 3);
+        set.insert(6, 8);
+        set.insert(3, 4);
+        assert_eq!(set.intervals(), vec![(1, 4), (6, 8)]);
+        set.insert(-5, 0);
+        set.insert(2, 7);
+        assert_eq!(set.intervals(), vec![(-5, 0), (1, 8)]);
+        set.insert(5, 5);
+        assert_eq!(set.intervals(), vec![(-5, 0), (1, 8)]);
+
+        set.remove(3, 5);
+        assert_eq!(set.intervals(), vec![(-5, 0), (1, 3), (5, 8)]);
+        set.remove(-1, 2);
+        assert_eq!(set.intervals(), vec![(-5, -1), (2, 3), (5, 8)]);
+        set.remove(-10, 6);
+        assert_eq!(set.intervals(), vec![(6, 8)]);
+
+        assert!(!set.contains(5));
+        assert!(set.contains(6));
+        assert!(set.contains(7));
+        assert!(!set.contains(8));
+    }
+
+    #[test]
+    fn interval_set_naive() {
+        let mut set = IntervalSet::new();
+        let mut naive = [false; 30];
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for _ in 0..500 {
+            let a = (rng.next_u64() % 30) as i64;
+            let b = (rng.next_u64() % 30) as i64;
+            let (l, r) = (a.min(b), a.max(b));
+            let insert = rng.next_u64() % 3 >= 1;
+            if insert {
+                set.insert(l, r);
+            } else {
+                set.remove(l, r);
+            }
+            for x in &mut naive[l as usize..r as usize] {
+                *x = insert;
+            }
+
+            for (x, &covered) in naive.iter().enumerate() {
+                assert_eq!(set.contains(x as i64), covered);
+            }
+            let mex = naive.iter().position(|&c| !c).unwrap_or(30) as i64;
+            assert_eq!(set.mex(), mex);
+        }
+    }
+
+    #[test]
+    fn interval_set_mex() {
+        let mut set = IntervalSet::new();
+        assert_eq!(set.mex(), 0);
+        set.insert(0, 3);
+        assert_eq!(set.mex(), 3);
+        set.insert(5, 7);
+        assert_eq!(set.mex(), 3);
+        set.insert(-4, -1);
+        assert_eq!(set.mex(), 3);
+        set.remove(1, 2);
+        assert_eq!(set.mex(), 1);
+        set.remove(0, 1);
+        assert_eq!(set.mex(), 0);
+    }
+}
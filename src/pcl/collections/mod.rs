@@ -1,5 +1,11 @@
 //! 各種のコレクションを定義する。
 
 pub mod counter;
+pub mod fast_hash;
+pub mod interval_set;
+pub mod running_median;
 
 pub use self::counter::Counter;
+pub use self::fast_hash::{FastHashMap, FastHashSet};
+pub use self::interval_set::IntervalSet;
+pub use self::running_median::RunningMedian;
@@ -0,0 +1,109 @@
+//! 要素を追加しながら中央値を求める `RunningMedian` を定義する。
+//!
+//! 小さい方の半分を最大ヒープに、大きい方の半分を最小ヒープに持っておく。
+//!
+//! # Example
+//!
+//! ```
+//! # use procon_lib::pcl::collections::running_median::RunningMedian;
+//! #
+//! let mut median = RunningMedian::new();
+//! assert_eq!(median.median(), None);
+//! median.insert(5);
+//! median.insert(1);
+//! assert_eq!(median.median(), Some(1));
+//! median.insert(3);
+//! assert_eq!(median.median(), Some(3));
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// 要素を追加しながら中央値を求めるコレクション。
+///
+/// 要素数が偶数のときは、中央の二つのうち小さい方 (下側中央値) を中央値とする。
+#[derive(Debug, Clone)]
+pub struct RunningMedian<T> {
+    /// 小さい方の半分。要素数は `upper` と等しいか、一つだけ多い。
+    lower: BinaryHeap<T>,
+    /// 大きい方の半分。
+    upper: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord + Copy> Default for RunningMedian<T> {
+    fn default() -> RunningMedian<T> {
+        RunningMedian::new()
+    }
+}
+
+impl<T: Ord + Copy> RunningMedian<T> {
+    /// 空のコレクションを生成する。
+    pub fn new() -> RunningMedian<T> {
+        RunningMedian {
+            lower: BinaryHeap::new(),
+            upper: BinaryHeap::new(),
+        }
+    }
+
+    /// 要素を追加する。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn insert(&mut self, x: T) {
+        match self.lower.peek() {
+            Some(&top) if x > top => self.upper.push(Reverse(x)),
+            _ => self.lower.push(x),
+        }
+
+        // 二つのヒープの大きさの差を整える。
+        if self.lower.len() > self.upper.len() + 1 {
+            let top = self.lower.pop().unwrap();
+            self.upper.push(Reverse(top));
+        } else if self.upper.len() > self.lower.len() {
+            let Reverse(top) = self.upper.pop().unwrap();
+            self.lower.push(top);
+        }
+    }
+
+    /// 中央値を返す。要素数が偶数なら下側中央値を返し、空なら `None` を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn median(&self) -> Option<T> {
+        self.lower.peek().cloned()
+    }
+
+    /// 要素数を返す。
+    pub fn len(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+
+    /// 空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_median() {
+        let values = [5, 15, 1, 3, 2, 8, 7, 9, 10, 6, 11, 4, 3, 3];
+        let expected = [5, 5, 5, 3, 3, 3, 5, 5, 7, 6, 7, 6, 6, 5];
+        let mut median = RunningMedian::new();
+        assert!(median.is_empty());
+        for (i, (&x, &m)) in values.iter().zip(&expected).enumerate() {
+            median.insert(x);
+            assert_eq!(median.median(), Some(m));
+            assert_eq!(median.len(), i + 1);
+
+            let mut sorted = values[..=i].to_vec();
+            sorted.sort();
+            assert_eq!(median.median(), Some(sorted[i / 2]));
+        }
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! - [`rtl!`](../../macro.rtl.html) ― 複合代入演算子を右辺から評価するマクロ。
 //! - [`matches!`](../../macro.matches.html) ― 標準の matches! と同様のもの (polyfill) 。
+//! - [`input!`](../../macro.input.html) ― 入力を読み込んで変数を宣言するマクロ。
 
 /// 複合代入演算子を右辺から評価するマクロ。
 ///
@@ -94,3 +95,159 @@ macro_rules! matches {
         }
     }
 }
+
+/// 入力を読み込み、指定した型の変数として宣言するマクロ。
+///
+/// proconio の `input!` を簡略化したもので、`polyfill::io` の関数を使って空白区切りのトークンを読み
+/// 込む。型としては次のものが書ける。
+///
+/// - `FromStr` を実装する型 ― トークンを一つ読み込んで解釈する。
+/// - `chars` ― トークンを一つ読み込み、`Vec<char>` として返す。
+/// - `bytes` ― トークンを一つ読み込み、`Vec<u8>` として返す。
+/// - `[T; n]` ― `T` を `n` 個読み込み、`Vec` として返す。`n` には先に読み込んだ変数も使える。
+/// - `(T, U, ...)` ― 各要素を順に読み込み、タプルとして返す。
+///
+/// 通常は標準入力から読み込むが、先頭に `from reader;` と書けば任意の `Read` から読み込める。
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate procon_lib;
+/// # use procon_lib::input;
+/// # fn main() {
+/// use std::io::Cursor;
+/// let mut source = Cursor::new("3\n1 -2 3\nabc\n2 2\n1 2\n3 4");
+/// input! {
+///     from &mut source;
+///     n: usize,
+///     a: [i64; n],
+///     s: chars,
+///     h: usize,
+///     w: usize,
+///     mut grid: [[u32; w]; h],
+/// }
+/// grid[0][0] += 10;
+/// assert_eq!(a, vec![1, -2, 3]);
+/// assert_eq!(s, vec!['a', 'b', 'c']);
+/// assert_eq!(grid, vec![vec![11, 2], vec![3, 4]]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! input {
+    // 読み込んだ値はブロックの中で順に束縛し、最後にタプルとしてまとめて外に出す。こうするとリーダ
+    // はブロックの外に残らない。
+    (@acc $r:ident $init:tt () () ()) => {};
+    (@acc $r:ident ($($init:tt)*) ($($pat:tt)*) ($($name:tt)*) ($($body:tt)*)) => {
+        let ($($pat)*) = {
+            #[allow(unused_mut)]
+            let mut $r = $($init)*;
+            $($body)*
+            ($($name)*)
+        };
+    };
+    (@acc $r:ident $init:tt $pat:tt $name:tt $body:tt , $($rest:tt)*) => {
+        $crate::input!(@acc $r $init $pat $name $body $($rest)*);
+    };
+    (@acc $r:ident $init:tt ($($pat:tt)*) ($($name:tt)*) ($($body:tt)*)
+        mut $v:ident : $t:tt $($rest:tt)*) => {
+        $crate::input!(
+            @acc $r $init
+            ($($pat)* mut $v,)
+            ($($name)* $v,)
+            ($($body)* let $v = $crate::input!(@read $r; $t);)
+            $($rest)*
+        );
+    };
+    (@acc $r:ident $init:tt ($($pat:tt)*) ($($name:tt)*) ($($body:tt)*)
+        $v:ident : $t:tt $($rest:tt)*) => {
+        $crate::input!(
+            @acc $r $init
+            ($($pat)* $v,)
+            ($($name)* $v,)
+            ($($body)* let $v = $crate::input!(@read $r; $t);)
+            $($rest)*
+        );
+    };
+    (@read $r:ident; chars) => {
+        $crate::pcl::polyfill::io::read_chars_from(&mut $r)
+    };
+    (@read $r:ident; bytes) => {
+        $crate::pcl::polyfill::io::read_bytes_from(&mut $r)
+    };
+    (@read $r:ident; [$t:tt; $n:expr]) => {
+        (0..$n)
+            .map(|_| $crate::input!(@read $r; $t))
+            .collect::<Vec<_>>()
+    };
+    (@read $r:ident; ($($t:tt),*)) => {
+        ($($crate::input!(@read $r; $t)),*)
+    };
+    (@read $r:ident; $t:ty) => {
+        $crate::pcl::polyfill::io::read_from::<_, $t>(&mut $r)
+    };
+    (from $reader:expr; $($rest:tt)*) => {
+        $crate::input!(@acc __reader ($reader) () () () $($rest)*);
+    };
+    // `Stdin` は読み込みのたびに短い間だけロックを取るので、続けて `input!` や `read()` を呼んでも
+    // デッドロックしない。
+    ($($rest:tt)*) => {
+        $crate::input!(@acc __reader (::std::io::stdin()) () () () $($rest)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    #[test]
+    fn input_from_reader() {
+        let mut source = Cursor::new("4 -7\n10 20 30 40\nhello xyz\n2\n1.5 2 x\n-3 4 y rest");
+        input! {
+            from &mut source;
+            n: usize,
+            k: i64,
+            a: [u64; n],
+            s: chars,
+            b: bytes,
+            m: usize,
+            mut pairs: [(f64, i32, char); m],
+        }
+        assert_eq!((n, k, m), (4, -7, 2));
+        assert_eq!(a, vec![10, 20, 30, 40]);
+        assert_eq!(s, vec!['h', 'e', 'l', 'l', 'o']);
+        assert_eq!(b, b"xyz".to_vec());
+        pairs.reverse();
+        assert_eq!(pairs, vec![(-3.0, 4, 'y'), (1.5, 2, 'x')]);
+
+        // 残りはそのまま読み込める。
+        input! { from &mut source; rest: String }
+        assert_eq!(rest, "rest");
+    }
+
+    #[test]
+    fn input_twice_in_a_row() {
+        let mut source = Cursor::new("1 2\n3");
+        input! { from &mut source; a: i32 }
+        input! { from &mut source; b: i32, c: i32 }
+        assert_eq!((a, b, c), (1, 2, 3));
+
+        // 標準入力のロックを持ち続けないので、続けて呼んでもロックを取り直せる。
+        input! {}
+        input! {}
+        let stdin = std::io::stdin();
+        drop(stdin.lock());
+    }
+
+    #[test]
+    fn input_nested_arrays() {
+        input! {
+            from Cursor::new("2 3\n1 2 3\n4 5 6\nab cd");
+            h: usize,
+            w: usize,
+            grid: [[i32; w]; h],
+            words: [chars; 2]
+        }
+        assert_eq!((h, w), (2, 3));
+        assert_eq!(grid, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(words, vec![vec!['a', 'b'], vec!['c', 'd']]);
+    }
+}
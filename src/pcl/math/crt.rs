@@ -0,0 +1,105 @@
+//! 中国剰余定理により連立合同式を解く `crt` を定義する。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::crt::crt;
+//! // use crate::pcl::math::crt::crt;
+//! // x ≡ 2 (mod 3), x ≡ 3 (mod 5), x ≡ 2 (mod 7)
+//! assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+//! // 法が互いに素でなくてもよい。
+//! assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+//! assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+//! ```
+
+/// 拡張ユークリッドの互除法。`a * x + b * y = g` となる `(g, x, y)` を返す。ただし `g = gcd(a, b)` 。
+fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = ext_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+/// `(余り, 法)` の組で与えられた連立合同式を解き、`(余り, 法の最小公倍数)` を返す。解がなければ
+/// `None` を返す。
+///
+/// 法は互いに素でなくてもよい。返される余りは `0 <= r < lcm` に正規化されている。空の入力に対して
+/// は `Some((0, 1))` を返す。
+///
+/// # オーバーフロー
+///
+/// 統合の途中の乗算は `i128` で行うため、途中で求まる法の最小公倍数が `i64` に収まる (つまりおおよ
+/// そ 9.2e18 以下である) 限りオーバーフローしない。最小公倍数がそれを超える場合の結果は保証しない。
+///
+/// # 計算量
+///
+/// 法の最大値を M として O(n log M)
+pub fn crt(residues: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut r0: i64 = 0;
+    let mut m0: i64 = 1;
+    for &(r1, m1) in residues {
+        assert!(m1 >= 1, "modulus must be positive: {}", m1);
+        let r1 = r1.rem_euclid(m1);
+
+        // m0 * p ≡ g (mod m1)
+        let (g, p, _) = ext_gcd(m0, m1);
+        if (r1 - r0) % g != 0 {
+            return None;
+        }
+
+        // x = r0 + m0 * t として m0 * t ≡ r1 - r0 (mod m1) を解く。
+        let m1g = m1 / g;
+        let t = (i128::from((r1 - r0) / g) * i128::from(p)).rem_euclid(i128::from(m1g));
+        let lcm = i128::from(m0) * i128::from(m1g);
+        r0 = (i128::from(r0) + i128::from(m0) * t).rem_euclid(lcm) as i64;
+        m0 = lcm as i64;
+    }
+
+    Some((r0, m0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(residues: &[(i64, i64)], (r, m): (i64, i64)) {
+        for &(ri, mi) in residues {
+            assert_eq!(r.rem_euclid(mi), ri.rem_euclid(mi));
+            assert_eq!(m % mi, 0);
+        }
+    }
+
+    #[test]
+    fn crt_coprime() {
+        let residues = [(2, 3), (3, 5), (2, 7)];
+        let res = crt(&residues).unwrap();
+        assert_eq!(res, (23, 105));
+        check(&residues, res);
+
+        let residues = [(-1, 1_000_000_007), (5, 998_244_353)];
+        let res = crt(&residues).unwrap();
+        assert_eq!(res.1, 1_000_000_007 * 998_244_353);
+        check(&residues, res);
+
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(7, 1)]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_non_coprime() {
+        let residues = [(1, 4), (3, 6)];
+        let res = crt(&residues).unwrap();
+        assert_eq!(res, (9, 12));
+        check(&residues, res);
+
+        let residues = [(2, 6), (8, 10), (8, 15)];
+        let res = crt(&residues).unwrap();
+        assert_eq!(res, (8, 30));
+        check(&residues, res);
+
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[(1, 2), (2, 4)]), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! 平面上の点 `Point` と、それを扱う幾何のアルゴリズムを定義する。
+//!
+//! 座標は `f64` で持つ。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::geometry::{closest_pair, Point};
+//! // use crate::pcl::math::geometry::{closest_pair, Point};
+//! let points = [
+//!     Point::new(0.0, 0.0),
+//!     Point::new(5.0, 5.0),
+//!     Point::new(1.0, 1.0),
+//!     Point::new(9.0, 0.0),
+//! ];
+//! let (i, j, d) = closest_pair(&points).unwrap();
+//! assert_eq!((i, j), (0, 2));
+//! assert!((d - 2f64.sqrt()).abs() < 1e-9);
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+/// 平面上の点 (あるいは位置ベクトル) 。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    /// 座標 `(x, y)` の点を生成する。
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    /// 内積を求める。
+    pub fn dot(self, other: Point) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// 外積 (の z 成分) を求める。`other` が `self` から見て反時計回り側にあれば正になる。
+    pub fn cross(self, other: Point) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// 原点からの距離 (ベクトルの長さ) を求める。
+    pub fn norm(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// 二点間の距離を求める。
+    pub fn dist(self, other: Point) -> f64 {
+        (self - other).norm()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/// 最も近い二点を分割統治法で求め、その添字 `(i, j)` (`i < j`) と距離を返す。
+///
+/// 点が二つ未満なら `None` を返す。同じ座標の点があれば距離 0 の組を返す。
+///
+/// # 計算量
+///
+/// O(n log n)
+pub fn closest_pair(points: &[Point]) -> Option<(usize, usize, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let mut idx: Vec<usize> = (0..points.len()).collect();
+    idx.sort_by(|&a, &b| cmp_f64(points[a].x, points[b].x));
+    let mut best = (0, 0, f64::INFINITY);
+    closest_pair_rec(points, &mut idx, &mut best);
+
+    let (i, j, d) = best;
+    Some((i.min(j), i.max(j), d))
+}
+
+/// `idx` は x 座標の昇順に並んでいるとする。`idx` の中の最近点対で `best` を更新し、`idx` を y 座標
+/// の昇順に並べ替える。
+fn closest_pair_rec(points: &[Point], idx: &mut [usize], best: &mut (usize, usize, f64)) {
+    let n = idx.len();
+    if n <= 1 {
+        return;
+    }
+
+    let mid = n / 2;
+    let mid_x = points[idx[mid]].x;
+    closest_pair_rec(points, &mut idx[..mid], best);
+    closest_pair_rec(points, &mut idx[mid..], best);
+
+    // 左右それぞれ y 座標の昇順になっているのでマージする。
+    let mut merged = Vec::with_capacity(n);
+    {
+        let (left, right) = idx.split_at(mid);
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() || j < right.len() {
+            let take_left =
+                j == right.len() || (i < left.len() && points[left[i]].y <= points[right[j]].y);
+            if take_left {
+                merged.push(left[i]);
+                i += 1;
+            } else {
+                merged.push(right[j]);
+                j += 1;
+            }
+        }
+    }
+    idx.copy_from_slice(&merged);
+
+    // 中央線からの距離が暫定の最小距離未満の点だけを、y 座標の順に近いもの同士で調べる。
+    let mut strip: Vec<usize> = Vec::new();
+    for &k in idx.iter() {
+        if (points[k].x - mid_x).abs() >= best.2 {
+            continue;
+        }
+        for &l in strip.iter().rev() {
+            if points[k].y - points[l].y >= best.2 {
+                break;
+            }
+            let d = points[k].dist(points[l]);
+            if d < best.2 {
+                *best = (l, k, d);
+            }
+        }
+        strip.push(k);
+    }
+}
+
+fn cmp_f64(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).expect("coordinates must not be NaN")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+
+    fn naive(points: &[Point]) -> Option<f64> {
+        let mut res: Option<f64> = None;
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let d = points[i].dist(points[j]);
+                res = Some(res.map_or(d, |r| r.min(d)));
+            }
+        }
+
+        res
+    }
+
+    #[test]
+    fn point_ops() {
+        let a = Point::new(3.0, 4.0);
+        let b = Point::new(1.0, -2.0);
+        assert_eq!(a + b, Point::new(4.0, 2.0));
+        assert_eq!(a - b, Point::new(2.0, 6.0));
+        assert_eq!(a.dot(b), -5.0);
+        assert_eq!(a.cross(b), -10.0);
+        assert_eq!(a.norm(), 5.0);
+        assert_eq!(a.dist(b), 40f64.sqrt());
+    }
+
+    #[test]
+    fn closest_pair_small() {
+        assert_eq!(closest_pair(&[]), None);
+        assert_eq!(closest_pair(&[Point::new(1.0, 2.0)]), None);
+
+        let points = [
+            Point::new(2.0, 3.0),
+            Point::new(12.0, 30.0),
+            Point::new(40.0, 50.0),
+            Point::new(5.0, 1.0),
+            Point::new(12.0, 10.0),
+            Point::new(3.0, 4.0),
+        ];
+        let (i, j, d) = closest_pair(&points).unwrap();
+        assert_eq!((i, j), (0, 5));
+        assert!((d - 2f64.sqrt()).abs() < 1e-9);
+
+        // 同じ座標の点
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(7.0, -1.0),
+            Point::new(3.0, 3.0),
+            Point::new(7.0, -1.0),
+        ];
+        assert_eq!(closest_pair(&points), Some((1, 3, 0.0)));
+    }
+
+    #[test]
+    fn closest_pair_matches_naive() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        let mut next = || (rng.next_u64() % 1000) as f64 / 10.0;
+
+        for n in 2..60 {
+            let points: Vec<_> = (0..n).map(|_| Point::new(next(), next())).collect();
+            let (i, j, d) = closest_pair(&points).unwrap();
+            assert!(i < j);
+            assert_eq!(points[i].dist(points[j]), d);
+            assert_eq!(Some(d), naive(&points));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! 数列の転倒数を求める `count_inversions_online` を定義する。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::inversion::count_inversions_online;
+//! // use crate::pcl::math::inversion::count_inversions_online;
+//! assert_eq!(count_inversions_online(&[3, 1, 2]), 2);
+//! assert_eq!(count_inversions_online(&["b", "a", "b", "a"]), 3);
+//! ```
+
+use crate::pcl::structure::SegmentTree;
+use crate::pcl::traits::math::group::Additive;
+
+/// 数列の転倒数、すなわち `i < j` かつ `a[i] > a[j]` となる組 `(i, j)` の個数を求める。
+///
+/// 値を座標圧縮して、各値の出現回数を持つセグメント木に左から順に要素を追加していく。各要素を追加
+/// する前に、既に追加された要素のうちそれより大きいものの個数を数えて足し合わせる。
+///
+/// # 計算量
+///
+/// O(n log n)
+pub fn count_inversions_online<T: Ord + Clone>(a: &[T]) -> u64 {
+    let mut values = a.to_vec();
+    values.sort();
+    values.dedup();
+
+    let mut counts = SegmentTree::from_array(vec![Additive(0u64); values.len()]);
+    let mut res = 0;
+    for x in a {
+        let rank = values.binary_search(x).expect("value must be in values");
+        res += counts.query(rank + 1..).0;
+        counts.apply(rank, Additive(1));
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+
+    /// マージソートで転倒数を数える。
+    fn merge_sort_count<T: Ord + Clone>(a: &mut Vec<T>) -> u64 {
+        if a.len() <= 1 {
+            return 0;
+        }
+
+        let mut right = a.split_off(a.len() / 2);
+        let mut left = a.clone();
+        let mut res = merge_sort_count(&mut left) + merge_sort_count(&mut right);
+
+        a.clear();
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() || j < right.len() {
+            if j == right.len() || (i < left.len() && left[i] <= right[j]) {
+                a.push(left[i].clone());
+                i += 1;
+            } else {
+                // left[i..] はすべて right[j] より大きい。
+                res += (left.len() - i) as u64;
+                a.push(right[j].clone());
+                j += 1;
+            }
+        }
+
+        res
+    }
+
+    #[test]
+    fn inversions_small() {
+        assert_eq!(count_inversions_online::<i32>(&[]), 0);
+        assert_eq!(count_inversions_online(&[1]), 0);
+        assert_eq!(count_inversions_online(&[1, 2, 3, 4]), 0);
+        assert_eq!(count_inversions_online(&[4, 3, 2, 1]), 6);
+        assert_eq!(count_inversions_online(&[2, 2, 2]), 0);
+        assert_eq!(count_inversions_online(&[2, 4, 1, 3, 5]), 3);
+    }
+
+    #[test]
+    fn inversions_match_merge_sort() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        for n in 0..60 {
+            let a: Vec<i64> = (0..n).map(|_| (rng.next_u64() % 20) as i64 - 10).collect();
+            let expected = merge_sort_count(&mut a.clone());
+            assert_eq!(count_inversions_online(&a), expected);
+        }
+
+        let a: Vec<u64> = (0..10000).map(|_| rng.next_u64()).collect();
+        assert_eq!(
+            count_inversions_online(&a),
+            merge_sort_count(&mut a.clone())
+        );
+    }
+}
@@ -0,0 +1,505 @@
+//! 行列 `Matrix` を定義する。
+//!
+//! 要素は `Zero` と `One` (したがって加法と乗法) を持つ型であればよく、例えば `Modint` を要素にし
+//! て線形漸化式の第 n 項を求めるのに使える。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::matrix::Matrix;
+//! # use procon_lib::pcl::math::Modint17 as M;
+//! // use crate::pcl::math::matrix::Matrix;
+//! // use crate::pcl::math::Modint17 as M;
+//! // フィボナッチ数列の第 n 項
+//! let a = Matrix::from_matrix(vec![vec![M::new(1), M::new(1)], vec![M::new(1), M::new(0)]]);
+//! assert_eq!(a.pow(10)[(0, 1)], M::new(55));
+//! ```
+
+use crate::pcl::compat::num::{One, Zero};
+use crate::pcl::math::modint::consts::ModintConst;
+use crate::pcl::math::modint::Modint;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// 行列。
+pub struct Matrix<T> {
+    data: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<_> = (0..self.height).map(|i| self.row(i)).collect();
+        f.debug_struct("Matrix").field("data", &rows).finish()
+    }
+}
+
+impl<T: Clone> Clone for Matrix<T> {
+    fn clone(&self) -> Self {
+        Matrix {
+            data: self.data.clone(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Matrix<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.height == other.height && self.width == other.width && self.data == other.data
+    }
+}
+
+impl<T: Eq> Eq for Matrix<T> {}
+
+impl<T> Matrix<T> {
+    /// 行数を取得する。
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// 列数を取得する。
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// `i` 行目を取得する。
+    pub fn row(&self, i: usize) -> &[T] {
+        assert!(i < self.height, "row index out of range: {}", i);
+        &self.data[i * self.width..(i + 1) * self.width]
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// 行のリストから行列を生成する。
+    pub fn from_matrix<M, A>(matrix: M) -> Matrix<T>
+    where
+        M: AsRef<[A]>,
+        A: AsRef<[T]>,
+    {
+        let rows = matrix.as_ref();
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.as_ref().len());
+        let mut data = Vec::with_capacity(height * width);
+        for row in rows {
+            let row = row.as_ref();
+            assert_eq!(
+                row.len(),
+                width,
+                "the matrix's length is differ line by line"
+            );
+            data.extend_from_slice(row);
+        }
+
+        Matrix {
+            data,
+            height,
+            width,
+        }
+    }
+}
+
+impl<T: Zero + One + Copy> Matrix<T> {
+    /// すべての要素が 0 である `height` 行 `width` 列の行列を生成する。
+    pub fn zeros(height: usize, width: usize) -> Matrix<T> {
+        Matrix {
+            data: vec![T::zero(); height * width],
+            height,
+            width,
+        }
+    }
+
+    /// `n` 次の単位行列を生成する。
+    pub fn identity(n: usize) -> Matrix<T> {
+        let mut res = Matrix::zeros(n, n);
+        for i in 0..n {
+            res[(i, i)] = T::one();
+        }
+
+        res
+    }
+
+    /// 行列の積を求める。`self` の列数と `rhs` の行数は等しくなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// `self` が n 行 m 列、`rhs` が m 行 l 列のとき O(nml)
+    pub fn mul(&self, rhs: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(
+            self.width, rhs.height,
+            "dimension mismatch: {}x{} * {}x{}",
+            self.height, self.width, rhs.height, rhs.width
+        );
+
+        let mut res = Matrix::zeros(self.height, rhs.width);
+        for i in 0..self.height {
+            for k in 0..self.width {
+                let a = self[(i, k)];
+                for j in 0..rhs.width {
+                    res[(i, j)] = res[(i, j)] + a * rhs[(k, j)];
+                }
+            }
+        }
+
+        res
+    }
+
+    /// 行列の累乗を求める。正方行列でなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// n 次正方行列に対し O(n^3 log exp)
+    pub fn pow(self, mut exp: u64) -> Matrix<T> {
+        assert_eq!(self.height, self.width, "the matrix is not square");
+
+        let mut base = self;
+        let mut res = Matrix::identity(base.height);
+        while exp > 0 {
+            if exp & 1 != 0 {
+                res = res.mul(&base);
+            }
+            base = base.mul(&base);
+            exp >>= 1;
+        }
+
+        res
+    }
+}
+
+/// 正方行列 `a` について、等比級数 I + A + A^2 + ... + A^(k-1) を求める。`k == 0` なら零行列を返
+/// す。
+///
+/// 部分和 S(m) と A^m から S(2m) = S(m) + A^m S(m) 、S(m + 1) = S(m) + A^m で倍々に求めるので、
+/// 逆行列 (I - A)^(-1) が存在しなくてもよい。
+///
+/// ```
+/// # use procon_lib::pcl::math::matrix::{matrix_geometric_sum, Matrix};
+/// # use procon_lib::pcl::math::Modint17 as M;
+/// // use crate::pcl::math::matrix::{matrix_geometric_sum, Matrix};
+/// // use crate::pcl::math::Modint17 as M;
+/// // 1 + 2 + 4 + 8 = 15
+/// let a = Matrix::from_matrix(vec![vec![M::new(2)]]);
+/// assert_eq!(matrix_geometric_sum(a, 4)[(0, 0)], M::new(15));
+/// ```
+///
+/// # 計算量
+///
+/// n 次正方行列に対し O(n^3 log k)
+pub fn matrix_geometric_sum<T: Zero + One + Copy>(a: Matrix<T>, k: u64) -> Matrix<T> {
+    assert_eq!(a.height, a.width, "the matrix is not square");
+
+    fn add<T: Zero + One + Copy>(x: &Matrix<T>, y: &Matrix<T>) -> Matrix<T> {
+        let mut res = x.clone();
+        for (r, &v) in res.data.iter_mut().zip(&y.data) {
+            *r = *r + v;
+        }
+
+        res
+    }
+
+    // k の上位の桁から順に見て、sum = S(m), pow = A^m を保つ。
+    let n = a.height;
+    let mut sum = Matrix::zeros(n, n);
+    let mut pow = Matrix::identity(n);
+    for bit in (0..64 - k.leading_zeros()).rev() {
+        sum = add(&sum, &pow.mul(&sum));
+        pow = pow.mul(&pow);
+        if k >> bit & 1 != 0 {
+            sum = add(&sum, &pow);
+            pow = pow.mul(&a);
+        }
+    }
+
+    sum
+}
+
+impl Matrix<i64> {
+    /// 整数行列の行列式を Bareiss のアルゴリズムで求める。正方行列でなければならない。
+    ///
+    /// 分数を使わずに消去を行い、途中の値はすべて元の行列の小行列式になるので整数のまま計算できる。
+    /// ただし途中の値や結果が `i64` に収まらなければオーバーフローする。全域木の数え上げ (行列木定
+    /// 理) のように値が大きくなりうる場合は、`Modint` を要素とする行列で `determinant()` を使うほ
+    /// うがよい。
+    ///
+    /// # 計算量
+    ///
+    /// O(n^3)
+    pub fn determinant_bareiss(&self) -> i64 {
+        assert_eq!(self.height, self.width, "the matrix is not square");
+
+        let n = self.height;
+        let mut a = self.clone();
+        let mut sign = 1;
+        let mut prev = 1;
+        for k in 0..n {
+            if a[(k, k)] == 0 {
+                // 0 でない要素を持つ行と入れ替える。なければ行列式は 0 。
+                let pivot = match (k + 1..n).find(|&i| a[(i, k)] != 0) {
+                    Some(i) => i,
+                    None => return 0,
+                };
+                for j in 0..n {
+                    a.data.swap(k * n + j, pivot * n + j);
+                }
+                sign = -sign;
+            }
+
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    // この割り算は必ず割り切れる。掛け算のオーバーフローを避けるため i128 で行う。
+                    let num = i128::from(a[(i, j)]) * i128::from(a[(k, k)])
+                        - i128::from(a[(i, k)]) * i128::from(a[(k, j)]);
+                    a[(i, j)] = (num / i128::from(prev)) as i64;
+                }
+            }
+            prev = a[(k, k)];
+        }
+
+        if n == 0 {
+            1
+        } else {
+            sign * a[(n - 1, n - 1)]
+        }
+    }
+}
+
+impl<C: ModintConst> Matrix<Modint<C>> {
+    /// 行列式を掃き出し法で求める。正方行列でなければならない。法は素数でなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// O(n^3)
+    pub fn determinant(&self) -> Modint<C> {
+        assert_eq!(self.height, self.width, "the matrix is not square");
+
+        let n = self.height;
+        let zero = Modint::new(0);
+        let mut a = self.clone();
+        let mut res = Modint::new(1);
+        for k in 0..n {
+            let pivot = match (k..n).find(|&i| a[(i, k)] != zero) {
+                Some(i) => i,
+                None => return zero,
+            };
+            if pivot != k {
+                for j in 0..n {
+                    a.data.swap(k * n + j, pivot * n + j);
+                }
+                res = -res;
+            }
+
+            res *= a[(k, k)];
+            let inv = a[(k, k)].inv();
+            for i in k + 1..n {
+                let factor = a[(i, k)] * inv;
+                if factor == zero {
+                    continue;
+                }
+                for j in k..n {
+                    let sub = factor * a[(k, j)];
+                    a[(i, j)] -= sub;
+                }
+            }
+        }
+
+        res
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(
+            i < self.height && j < self.width,
+            "index out of range: ({}, {})",
+            i,
+            j
+        );
+        &self.data[i * self.width + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(
+            i < self.height && j < self.width,
+            "index out of range: ({}, {})",
+            i,
+            j
+        );
+        &mut self.data[i * self.width + j]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::math::Modint17 as M;
+    use crate::pcl::test_util::XorShift;
+
+    #[test]
+    fn matrix_mul() {
+        let a = Matrix::from_matrix(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from_matrix(vec![vec![1, 0], vec![0, 1], vec![2, -1]]);
+        assert_eq!(
+            a.mul(&b),
+            Matrix::from_matrix(vec![vec![7, -1], vec![16, -1]])
+        );
+        assert_eq!(a.mul(&Matrix::identity(3)), a);
+        assert_eq!(Matrix::identity(2).mul(&a), a);
+    }
+
+    #[test]
+    fn matrix_geometric_sum_naive() {
+        let a = Matrix::from_matrix(vec![
+            vec![M::new(1), M::new(2), M::new(0)],
+            vec![M::new(3), M::new(-1), M::new(5)],
+            vec![M::new(0), M::new(7), M::new(2)],
+        ]);
+        let mut expected = Matrix::zeros(3, 3);
+        let mut pow = Matrix::identity(3);
+        for k in 0..40 {
+            assert_eq!(matrix_geometric_sum(a.clone(), k), expected);
+            for i in 0..3 {
+                for j in 0..3 {
+                    expected[(i, j)] += pow[(i, j)];
+                }
+            }
+            pow = pow.mul(&a);
+        }
+
+        // I - A が正則でない場合 (A = I) でも求まる。
+        let sum = matrix_geometric_sum(Matrix::<M>::identity(2), 1_000_000_000_000);
+        assert_eq!(sum[(0, 0)], M::new(1_000_000_000_000));
+        assert_eq!(sum[(0, 1)], M::new(0));
+        assert_eq!(
+            matrix_geometric_sum(Matrix::<M>::zeros(0, 0), 5),
+            Matrix::zeros(0, 0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn matrix_mul_dimension_mismatch() {
+        let a = Matrix::from_matrix(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        a.mul(&a);
+    }
+
+    #[test]
+    fn matrix_pow_fibonacci() {
+        let a = Matrix::from_matrix(vec![vec![M::new(1), M::new(1)], vec![M::new(1), M::new(0)]]);
+        assert_eq!(a.clone().pow(0), Matrix::identity(2));
+        assert_eq!(a.clone().pow(1), a);
+
+        let mut fib = (M::new(0), M::new(1));
+        for n in 0..200 {
+            assert_eq!(a.clone().pow(n)[(0, 1)], fib.0);
+            fib = (fib.1, fib.0 + fib.1);
+        }
+
+        // F(10^18) mod 1e9+7
+        assert_eq!(
+            a.pow(1_000_000_000_000_000_000)[(0, 1)],
+            M::new(209_783_453)
+        );
+    }
+
+    #[test]
+    fn matrix_determinant_bareiss() {
+        let a = Matrix::from_matrix(vec![vec![2, -3, 1], vec![2, 0, -1], vec![1, 4, 5]]);
+        assert_eq!(a.determinant_bareiss(), 49);
+
+        // 左上が 0 なので行の入れ替えが必要
+        let a = Matrix::from_matrix(vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 9]]);
+        assert_eq!(a.determinant_bareiss(), -3);
+
+        let singular = Matrix::from_matrix(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        assert_eq!(singular.determinant_bareiss(), 0);
+        assert_eq!(Matrix::<i64>::identity(5).determinant_bareiss(), 1);
+        assert_eq!(Matrix::<i64>::zeros(0, 0).determinant_bareiss(), 1);
+
+        // 行列木定理: 完全グラフ K_5 の全域木は 5^3 = 125 個
+        let mut laplacian = Matrix::zeros(4, 4);
+        for i in 0..4 {
+            for j in 0..4 {
+                laplacian[(i, j)] = if i == j { 4 } else { -1 };
+            }
+        }
+        assert_eq!(laplacian.determinant_bareiss(), 125);
+    }
+
+    #[test]
+    fn matrix_determinant_bareiss_matches_naive() {
+        // 置換を全列挙して行列式を求める。
+        fn naive(a: &Matrix<i64>) -> i64 {
+            fn rec(a: &Matrix<i64>, row: usize, used: &mut [bool], sign: i64) -> i64 {
+                let n = a.height();
+                if row == n {
+                    return sign;
+                }
+
+                let mut res = 0;
+                let mut sign = sign;
+                for j in 0..n {
+                    if used[j] {
+                        continue;
+                    }
+                    used[j] = true;
+                    res += a[(row, j)] * rec(a, row + 1, used, sign);
+                    used[j] = false;
+                    // 残っている列のうち何番目を選んだかで符号が変わる。
+                    sign = -sign;
+                }
+
+                res
+            }
+
+            rec(a, 0, &mut vec![false; a.height()], 1)
+        }
+
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        let mut next = || (rng.next_u64() % 7) as i64 - 3;
+
+        for n in 1..6 {
+            for _ in 0..50 {
+                let rows: Vec<Vec<i64>> =
+                    (0..n).map(|_| (0..n).map(|_| next()).collect()).collect();
+                let a = Matrix::from_matrix(rows);
+                assert_eq!(a.determinant_bareiss(), naive(&a));
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_determinant_modint() {
+        let rows = [[2, -3, 1], [2, 0, -1], [1, 4, 5]];
+        let a = Matrix::from_matrix(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| M::new(x)).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(a.determinant(), M::new(49));
+
+        let a = Matrix::from_matrix(vec![vec![M::new(0), M::new(1)], vec![M::new(1), M::new(0)]]);
+        assert_eq!(a.determinant(), M::new(-1));
+        assert_eq!(Matrix::<M>::zeros(3, 3).determinant(), M::new(0));
+        assert_eq!(Matrix::<M>::identity(0).determinant(), M::new(1));
+
+        // 整数の行列式を法で割った余りと一致する。
+        let mut rng = XorShift::new(2_463_534_242);
+        let mut next = || (rng.next_u64() % 21) as i64 - 10;
+        for n in 1..7 {
+            let rows: Vec<Vec<i64>> = (0..n).map(|_| (0..n).map(|_| next()).collect()).collect();
+            let modint_rows: Vec<Vec<M>> = rows
+                .iter()
+                .map(|row| row.iter().map(|&x| M::new(x)).collect())
+                .collect();
+            assert_eq!(
+                Matrix::from_matrix(modint_rows).determinant(),
+                M::new(Matrix::from_matrix(rows).determinant_bareiss())
+            );
+        }
+    }
+}
@@ -1,7 +1,15 @@
 //! 各種の数学的なアルゴリズムを定義する。
 
+pub mod crt;
+pub mod geometry;
+pub mod inversion;
+pub mod matrix;
 pub mod modint;
 pub mod sum;
 
+pub use self::crt::crt;
+pub use self::geometry::{closest_pair, Point};
+pub use self::inversion::count_inversions_online;
+pub use self::matrix::{matrix_geometric_sum, Matrix};
 pub use self::modint::{Modint, Modint17};
-pub use self::sum::{CumSum, CumSum2D};
+pub use self::sum::{CumSum, CumSum2D, Imos, Imos2D};
@@ -0,0 +1,83 @@
+//! 二項係数を扱う関数を定義する。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::modint::Modint17 as M;
+//! # use procon_lib::pcl::math::modint::binomial::binomial_table;
+//! // use crate::pcl::math::modint::Modint17 as M;
+//! // use crate::pcl::math::modint::binomial::binomial_table;
+//! let table = binomial_table(5);
+//! assert_eq!(table[5][2], M::new(10));
+//! assert_eq!(table[4].len(), 5);
+//! ```
+
+use super::consts::ModintConst;
+use super::Modint;
+
+/// パスカルの三角形を `n` 行目まで作る。`table[i][j]` (`j <= i`) は二項係数 C(i, j) になる。
+///
+/// 加算しか使わないので逆元が不要で、法が素数でなくてもよい。n が小さく階乗を前計算するまでもない
+/// ときに使う。
+///
+/// # 計算量
+///
+/// O(n^2)
+pub fn binomial_table<C: ModintConst>(n: usize) -> Vec<Vec<Modint<C>>> {
+    let mut table: Vec<Vec<Modint<C>>> = Vec::with_capacity(n + 1);
+    for i in 0..=n {
+        let mut row = vec![Modint::new(1); i + 1];
+        for j in 1..i {
+            row[j] = table[i - 1][j - 1] + table[i - 1][j];
+        }
+        table.push(row);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::math::modint::Modint17;
+
+    define_modint_const! {
+        pub const ModSmall = 13;
+    }
+
+    define_modint_const! {
+        pub const ModComposite = 1_000_000;
+    }
+
+    #[test]
+    fn binomial_table_matches_factorials() {
+        let n = 60;
+        let table = binomial_table(n);
+        let mut fact = vec![Modint17::new(1); n + 1];
+        for i in 1..=n {
+            fact[i] = fact[i - 1] * Modint17::new(i as i64);
+        }
+        for i in 0..=n {
+            assert_eq!(table[i].len(), i + 1);
+            for j in 0..=i {
+                assert_eq!(table[i][j], fact[i] / (fact[j] * fact[i - j]));
+            }
+        }
+        assert_eq!(
+            table[60][30],
+            Modint17::new(118_264_581_564_861_424 % 1_000_000_007)
+        );
+    }
+
+    #[test]
+    fn binomial_table_small_and_composite_modulus() {
+        // 法以上の n でも階乗の逆元を使わないので正しく求まる。
+        let table = binomial_table::<ModSmall>(20);
+        assert_eq!(table[13][1], Modint::new(0));
+        assert_eq!(table[20][10], Modint::new(184_756 % 13));
+
+        let table = binomial_table::<ModComposite>(40);
+        assert_eq!(table[40][20], Modint::new(137_846_528_820 % 1_000_000));
+        assert_eq!(table[0], vec![Modint::new(1)]);
+    }
+}
@@ -33,6 +33,10 @@
 /// `Modint` の法になる定数を定めるマクロを提供する。
 #[macro_use]
 pub mod consts;
+pub mod binomial;
+pub mod ntt;
+pub mod recurrence;
+pub mod transition;
 
 #[cfg(feature = "crates-atc-2020")]
 use num::Num;
@@ -40,6 +44,7 @@ use num::Num;
 use self::consts::ModintConst;
 use crate::pcl::compat::num::{One, Zero};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::iter::{Product, Sum};
@@ -91,18 +96,31 @@ impl<C> Modint<C> {
     pub fn inner(self) -> ModintInnerType {
         self.value
     }
+
+    /// 中身の値をリトルエンディアンのバイト列に変換する。
+    ///
+    /// 値は常に `0 <= value < MOD` に正規化されているので、同じ値なら同じバイト列になる。`Modint`
+    /// の列をハッシュする場合などに使う。
+    pub fn to_le_bytes(self) -> [u8; 8] {
+        self.value.to_le_bytes()
+    }
 }
 
 impl<C: ModintConst> Modint<C> {
     /// 新しい `Modint` を作成する。値は最初に丸められる。
-    pub fn new(mut value: ModintInnerType) -> Modint<C> {
+    pub fn new(value: ModintInnerType) -> Modint<C> {
         assert_ne!(C::MOD, 0, "MOD is 0");
+        let mut value = value % C::MOD;
         if value < 0 {
-            let m = (-value) / C::MOD;
-            value += (m + 1) * C::MOD;
+            value += C::MOD;
         }
 
-        unsafe { Modint::new_unchecked(value % C::MOD) }
+        unsafe { Modint::new_unchecked(value) }
+    }
+
+    /// `to_le_bytes()` で得たバイト列から `Modint` を復元する。値は丸められる。
+    pub fn from_le_bytes(bytes: [u8; 8]) -> Modint<C> {
+        Modint::new(ModintInnerType::from_le_bytes(bytes))
     }
 
     /// 逆元を求める。
@@ -121,6 +139,51 @@ impl<C: ModintConst> Modint<C> {
 
         Modint::new(u)
     }
+
+    /// 累乗を求める。
+    ///
+    /// # 計算量
+    ///
+    /// O(log exp)
+    pub fn pow(self, mut exp: u64) -> Modint<C> {
+        let mut base = self;
+        let mut res = Modint::new(1);
+        while exp > 0 {
+            if exp & 1 != 0 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+
+        res
+    }
+
+    /// 負の指数も許す累乗を求める。指数が負なら逆元の累乗を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log MOD + log |exp|)
+    pub fn pow_signed(self, exp: i64) -> Modint<C> {
+        if exp >= 0 {
+            self.pow(exp as u64)
+        } else {
+            // -exp は i64::MIN でオーバーフローするので、u64 にしてから符号を反転する。
+            self.inv().pow((exp as u64).wrapping_neg())
+        }
+    }
+
+    /// 絶対値が最も小さくなる代表元を返す。すなわち `(-MOD/2, MOD/2]` の範囲の値を返す。
+    ///
+    /// デバッグ時に `1000000006` ではなく `-1` のように表示したいときに使う。`Display` の表示は変わ
+    /// らない。
+    pub fn balanced(self) -> ModintInnerType {
+        if self.value > C::MOD / 2 {
+            self.value - C::MOD
+        } else {
+            self.value
+        }
+    }
 }
 
 impl<C: ModintConst> PartialEq for Modint<C> {
@@ -165,9 +228,12 @@ impl<C> Copy for Modint<C> {}
 
 impl<C: ModintConst> AddAssign for Modint<C> {
     fn add_assign(&mut self, rhs: Modint<C>) {
-        self.value += rhs.value;
-        if self.value >= C::MOD {
-            self.value -= C::MOD;
+        // self.value + rhs.value がオーバーフローしないよう、MOD を超えるかどうかを先に判定する。
+        let rest = C::MOD - rhs.value;
+        if self.value >= rest {
+            self.value -= rest;
+        } else {
+            self.value += rhs.value;
         }
     }
 }
@@ -183,8 +249,9 @@ impl<C: ModintConst> SubAssign for Modint<C> {
 
 impl<C: ModintConst> MulAssign for Modint<C> {
     fn mul_assign(&mut self, rhs: Modint<C>) {
-        self.value *= rhs.value;
-        self.value %= C::MOD;
+        // MOD が大きいと積が ModintInnerType に収まらないので、i128 で計算してから丸める。
+        let prod = i128::from(self.value) * i128::from(rhs.value);
+        self.value = (prod % i128::from(C::MOD)) as ModintInnerType;
     }
 }
 
@@ -277,6 +344,106 @@ impl<C: ModintConst> Num for Modint<C> {
     }
 }
 
+/// 与えられた値すべての逆元をまとめて求める。
+///
+/// 累積積をとってから全体の積の逆元を一度だけ求め、逆順にほどいていく。いずれかの要素が 0 であれ
+/// ば、`DivAssign` と同様に panic する。
+///
+/// # 計算量
+///
+/// O(n + log MOD)
+pub fn inv_all<C: ModintConst>(values: &[Modint<C>]) -> Vec<Modint<C>> {
+    // prefix[i] = values[0] * ... * values[i - 1]
+    let mut prefix = Vec::with_capacity(values.len() + 1);
+    prefix.push(Modint::new(1));
+    for (i, &value) in values.iter().enumerate() {
+        if value.value == 0 {
+            panic!("attempted to divide by zero");
+        }
+        let next = prefix[i] * value;
+        prefix.push(next);
+    }
+
+    // acc は常に values[0] * ... * values[i] の逆元
+    let mut acc = prefix[values.len()].inv();
+    let mut res = vec![Modint::new(0); values.len()];
+    for i in (0..values.len()).rev() {
+        res[i] = acc * prefix[i];
+        acc *= values[i];
+    }
+
+    res
+}
+
+/// 二つの列の内積を求める。
+///
+/// 積の和を `u128` に溜めておき、オーバーフローしそうになったときだけ剰余をとるので、要素ごとに剰
+/// 余をとるよりも `%` の回数が少なくて済む。二つの列の長さは等しくなければならない。
+///
+/// # 計算量
+///
+/// O(n)
+pub fn dot<C: ModintConst>(a: &[Modint<C>], b: &[Modint<C>]) -> Modint<C> {
+    debug_assert_eq!(a.len(), b.len(), "the lengths of the two slices differ");
+    let modulus = C::MOD as u128;
+    let max_prod = (modulus - 1) * (modulus - 1);
+    // acc がこれ以下であれば、積をもう一つ足してもオーバーフローしない。
+    let limit = !0u128 - max_prod;
+
+    let mut acc: u128 = 0;
+    for (x, y) in a.iter().zip(b) {
+        if acc > limit {
+            acc %= modulus;
+        }
+        acc += x.value as u128 * y.value as u128;
+    }
+
+    Modint::new((acc % modulus) as ModintInnerType)
+}
+
+/// `base^x = target` となる最小の非負整数 `x` を求める (離散対数) 。存在しなければ `None` を返す。
+///
+/// Baby-step Giant-step による。法 `C::MOD` は素数であることを仮定している。`target` が 1 なら
+/// `Some(0)` を返す。`base` が 0 の場合は `target` が 0 なら `Some(1)` 、それ以外なら `None` を返す。
+///
+/// # 計算量
+///
+/// O(sqrt(MOD))
+pub fn log<C: ModintConst>(base: Modint<C>, target: Modint<C>) -> Option<u64> {
+    let one = Modint::new(1);
+    if target == one {
+        return Some(0);
+    }
+    if base.value == 0 {
+        return if target.value == 0 { Some(1) } else { None };
+    }
+
+    let mut m = (C::MOD as f64).sqrt() as ModintInnerType;
+    while m * m < C::MOD {
+        m += 1;
+    }
+
+    // baby-step: base^j -> j (同じ値なら小さい j を優先する)
+    let mut table = HashMap::new();
+    let mut cur = one;
+    for j in 0..m {
+        table.entry(cur.value).or_insert(j);
+        cur *= base;
+    }
+
+    // giant-step: target * base^(-im) が base^j に一致すれば x = im + j
+    let giant = cur.inv();
+    let mut gamma = target;
+    for i in 0..m {
+        if let Some(&j) = table.get(&gamma.value) {
+            return Some((i * m + j) as u64);
+        }
+        gamma *= giant;
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,4 +501,145 @@ mod tests {
         assert_eq!(cs.sum(1..).0, M::new(1));
         assert_eq!(cs.sum(..2).0, M::new(2));
     }
+
+    #[test]
+    fn modint_balanced() {
+        assert_eq!(Modint17::new(-1).balanced(), -1);
+        assert_eq!(Modint17::new(-500_000_003).balanced(), -500_000_003);
+        assert_eq!(Modint17::new(500_000_003).balanced(), 500_000_003);
+        assert_eq!(Modint17::new(12345).balanced(), 12345);
+        assert_eq!(Modint17::new(0).balanced(), 0);
+
+        let balanced: Vec<_> = (0..5).map(|x| M::new(x).balanced()).collect();
+        assert_eq!(balanced, vec![0, 1, 2, -2, -1]);
+    }
+
+    #[test]
+    fn modint_pow_signed() {
+        for &x in &[1, 2, 3, 12345, 1_000_000_006] {
+            let a = Modint17::new(x);
+            assert_eq!(a.pow_signed(-1), a.inv());
+            assert_eq!(a.pow_signed(0), Modint17::new(1));
+            assert_eq!(a.pow_signed(10), a.pow(10));
+            assert_eq!(a.pow_signed(-10), a.inv().pow(10));
+            assert_eq!(a.pow_signed(-7) * a.pow_signed(7), Modint17::new(1));
+            assert_eq!(a.pow_signed(-(1 << 62) * 2), a.inv().pow(1 << 63));
+        }
+    }
+
+    #[test]
+    fn modint_le_bytes() {
+        for &x in [0, 1, 255, 256, 123_456_789, 1_000_000_006].iter() {
+            let m = Modint17::new(x);
+            assert_eq!(m.to_le_bytes(), x.to_le_bytes());
+            assert_eq!(Modint17::from_le_bytes(m.to_le_bytes()), m);
+        }
+
+        assert_eq!(
+            Modint17::new(-1).to_le_bytes(),
+            [0x06, 0xca, 0x9a, 0x3b, 0, 0, 0, 0]
+        );
+        assert_eq!(M::from_le_bytes([7, 0, 0, 0, 0, 0, 0, 0]), M::new(2));
+    }
+
+    #[test]
+    fn modint_inv_all() {
+        let values: Vec<_> = (1..5).map(M::new).collect();
+        let invs = inv_all(&values);
+        for (&x, &inv) in values.iter().zip(&invs) {
+            assert_eq!(inv, x.inv());
+            assert_eq!(x * inv, M::new(1));
+        }
+
+        let values: Vec<_> = (1..1000).map(|x| Modint17::new(x * x * 7919)).collect();
+        let invs = inv_all(&values);
+        for (&x, &inv) in values.iter().zip(&invs) {
+            assert_eq!(inv, x.inv());
+        }
+
+        assert!(inv_all::<Mod5>(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn modint_inv_all_zero() {
+        inv_all(&[M::new(1), M::new(0), M::new(2)]);
+    }
+
+    #[test]
+    fn modint_large_modulus() {
+        define_modint_const! {
+            pub const Mod31 = 2_147_483_647;
+        }
+        define_modint_const! {
+            pub const Mod61 = 2_305_843_009_213_693_951;
+        }
+
+        type M31 = Modint<Mod31>;
+        let a = M31::new(2_147_483_646);
+        assert_eq!(a * a, M31::new(1));
+        assert_eq!(a * a.inv(), M31::new(1));
+        assert_eq!(
+            M31::new(123_456_789) * M31::new(987_654_321),
+            M31::new(2_137_109_934)
+        );
+
+        // 2^61 - 1 では積が i64 に収まらない。
+        type M61 = Modint<Mod61>;
+        let b = M61::new(2_305_843_009_213_693_950);
+        assert_eq!(b * b, M61::new(1));
+        assert_eq!(b + b, M61::new(2_305_843_009_213_693_949));
+        assert_eq!(M61::new(1 << 40) * M61::new(1 << 40), M61::new(1 << 19));
+        assert_eq!(M61::new(-1), b);
+        assert_eq!(M61::new(3).pow(2_305_843_009_213_693_950), M61::new(1));
+    }
+
+    #[test]
+    fn modint_log() {
+        define_modint_const! {
+            pub const Mod13 = 13;
+        }
+        type M13 = Modint<Mod13>;
+
+        assert_eq!(log(M13::new(2), M13::new(1)), Some(0));
+        assert_eq!(log(M13::new(2), M13::new(8)), Some(3));
+        assert_eq!(log(M13::new(2), M13::new(7)), Some(11));
+        assert_eq!(log(M13::new(3), M13::new(2)), None);
+        assert_eq!(log(M13::new(0), M13::new(0)), Some(1));
+        assert_eq!(log(M13::new(0), M13::new(5)), None);
+        assert_eq!(log(M13::new(0), M13::new(1)), Some(0));
+
+        // 総当たりと比較する。
+        for b in 0..13 {
+            for t in 0..13 {
+                let (b, t) = (M13::new(b), M13::new(t));
+                let expected = (0..13).find(|&x| b.pow(x) == t);
+                assert_eq!(log(b, t), expected);
+            }
+        }
+
+        let x = log(Modint17::new(5), Modint17::new(123_456_789)).unwrap();
+        assert_eq!(Modint17::new(5).pow(x), Modint17::new(123_456_789));
+    }
+
+    #[test]
+    fn modint_dot() {
+        let a: Vec<_> = (0..1000)
+            .map(|x| Modint17::new(1_000_000_006 - x))
+            .collect();
+        let b: Vec<_> = (0..1000).map(|x| Modint17::new(x * x * 31 + 7)).collect();
+        let naive = a.iter().zip(&b).map(|(&x, &y)| x * y).sum::<Modint17>();
+        assert_eq!(dot(&a, &b), naive);
+        assert_eq!(dot::<Mod17>(&[], &[]), Modint17::new(0));
+
+        // 積が大きく、何度も途中で剰余をとる必要がある場合
+        define_modint_const! {
+            pub const Mod61 = 2_305_843_009_213_693_951;
+        }
+        type M61 = Modint<Mod61>;
+        let a: Vec<_> = (0..1000).map(|x| M61::new(-1 - x)).collect();
+        let b: Vec<_> = (0..1000).map(|x| M61::new(-7 - x * 3)).collect();
+        let naive = a.iter().zip(&b).map(|(&x, &y)| x * y).sum::<M61>();
+        assert_eq!(dot(&a, &b), naive);
+    }
 }
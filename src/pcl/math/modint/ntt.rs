@@ -0,0 +1,355 @@
+//! 数論変換 (Number Theoretic Transform) と、それを利用した畳み込みを定義する。
+//!
+//! 法 `C::MOD` が `k * 2^m + 1` の形の素数であるときに限り、長さ 2^m までの数列を変換できる。原始根
+//! は内部で求めるので与える必要はない。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::modint::Modint998244353 as M;
+//! # use procon_lib::pcl::math::modint::ntt::convolution;
+//! // use crate::pcl::math::modint::Modint998244353 as M;
+//! // use crate::pcl::math::modint::ntt::convolution;
+//! let a = [M::new(1), M::new(2), M::new(3)];
+//! let b = [M::new(4), M::new(5)];
+//! assert_eq!(
+//!     convolution(&a, &b),
+//!     vec![M::new(4), M::new(13), M::new(22), M::new(15)]
+//! );
+//! ```
+
+use super::consts::ModintConst;
+use super::{Modint, ModintInnerType};
+use std::fmt;
+
+/// 法 `C::MOD` の原始根を求める。
+///
+/// `C::MOD - 1` を素因数分解し、すべての素因数 q について g^((MOD-1)/q) ≠ 1 となる最小の g を探す。
+fn primitive_root<C: ModintConst>() -> Modint<C> {
+    let mut factors = Vec::new();
+    let mut rest = C::MOD - 1;
+    let mut q = 2;
+    while q * q <= rest {
+        if rest % q == 0 {
+            factors.push(q);
+            while rest % q == 0 {
+                rest /= q;
+            }
+        }
+        q += 1;
+    }
+    if rest > 1 {
+        factors.push(rest);
+    }
+
+    let mut g: ModintInnerType = 2;
+    loop {
+        let cand = Modint::new(g);
+        if factors
+            .iter()
+            .all(|&q| cand.pow(((C::MOD - 1) / q) as u64) != Modint::new(1))
+        {
+            return cand;
+        }
+        g += 1;
+    }
+}
+
+/// 数論変換に使う 1 の冪根とビット反転順の並べ替えを前計算しておく。
+///
+/// 同じ法で何度も変換や畳み込みを行う場合に、毎回回転因子を計算し直さずに済む。前計算した最大の長さ
+/// `max_len` 以下の 2 の冪の長さの数列を変換できる。
+///
+/// ```
+/// # use procon_lib::pcl::math::modint::Modint998244353 as M;
+/// # use procon_lib::pcl::math::modint::ntt::NttContext;
+/// // use crate::pcl::math::modint::Modint998244353 as M;
+/// // use crate::pcl::math::modint::ntt::NttContext;
+/// let ctx = NttContext::new(8);
+/// let a = [M::new(1), M::new(1)];
+/// let b = ctx.convolution(&a, &a);
+/// assert_eq!(b, vec![M::new(1), M::new(2), M::new(1)]);
+/// assert_eq!(
+///     ctx.convolution(&b, &a),
+///     vec![M::new(1), M::new(3), M::new(3), M::new(1)]
+/// );
+/// ```
+pub struct NttContext<C> {
+    log: u32,
+    /// `roots[k]` は 1 の原始 `max_len` 乗根の `k` 乗 (`0 <= k < max_len / 2`) 。
+    roots: Vec<Modint<C>>,
+    /// `roots` の各要素の逆元。
+    inv_roots: Vec<Modint<C>>,
+    /// 長さ `max_len` でのビット反転。
+    rev: Vec<usize>,
+}
+
+impl<C> fmt::Debug for NttContext<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NttContext")
+            .field("max_len", &(1usize << self.log))
+            .finish()
+    }
+}
+
+impl<C> Clone for NttContext<C> {
+    fn clone(&self) -> Self {
+        NttContext {
+            log: self.log,
+            roots: self.roots.clone(),
+            inv_roots: self.inv_roots.clone(),
+            rev: self.rev.clone(),
+        }
+    }
+}
+
+impl<C: ModintConst> NttContext<C> {
+    /// 長さ `max_len` までの変換に必要な値を前計算する。`max_len` は 2 の冪に切り上げられる。
+    ///
+    /// # 計算量
+    ///
+    /// O(max_len)
+    pub fn new(max_len: usize) -> NttContext<C> {
+        let n = max_len.next_power_of_two();
+        assert!(
+            (C::MOD - 1) % n as ModintInnerType == 0,
+            "MOD - 1 is not divisible by the length {}",
+            n
+        );
+        let log = n.trailing_zeros();
+
+        let w = primitive_root::<C>().pow(((C::MOD - 1) / n as ModintInnerType) as u64);
+        let w_inv = w.inv();
+        let mut roots = Vec::with_capacity(n / 2);
+        let mut inv_roots = Vec::with_capacity(n / 2);
+        let mut wk = Modint::new(1);
+        let mut wk_inv = Modint::new(1);
+        for _ in 0..n / 2 {
+            roots.push(wk);
+            inv_roots.push(wk_inv);
+            wk *= w;
+            wk_inv *= w_inv;
+        }
+
+        let mut rev = vec![0; n];
+        for i in 1..n {
+            rev[i] = (rev[i >> 1] >> 1) | ((i & 1) << log.saturating_sub(1));
+        }
+
+        NttContext {
+            log,
+            roots,
+            inv_roots,
+            rev,
+        }
+    }
+
+    /// 前計算した最大の長さを返す。
+    pub fn max_len(&self) -> usize {
+        1 << self.log
+    }
+
+    /// 数論変換を行う。長さは `max_len()` 以下の 2 の冪でなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// O(n log n)
+    pub fn transform(&self, a: &mut [Modint<C>]) {
+        self.butterfly(a, &self.roots);
+    }
+
+    /// 数論変換の逆変換を行う。長さは `max_len()` 以下の 2 の冪でなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// O(n log n)
+    pub fn inverse_transform(&self, a: &mut [Modint<C>]) {
+        self.butterfly(a, &self.inv_roots);
+
+        let n_inv = Modint::new(a.len() as ModintInnerType).inv();
+        for x in a.iter_mut() {
+            *x *= n_inv;
+        }
+    }
+
+    /// 二つの数列の畳み込みを求める。
+    ///
+    /// 結果の長さは `a.len() + b.len() - 1` (どちらかが空なら空) で、これを 2 の冪に切り上げた値が
+    /// `max_len()` 以下でなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// n = a.len() + b.len() として O(n log n)
+    pub fn convolution(&self, a: &[Modint<C>], b: &[Modint<C>]) -> Vec<Modint<C>> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+
+        let res_len = a.len() + b.len() - 1;
+        let n = res_len.next_power_of_two();
+
+        let mut fa = a.to_vec();
+        fa.resize(n, Modint::new(0));
+        let mut fb = b.to_vec();
+        fb.resize(n, Modint::new(0));
+
+        self.transform(&mut fa);
+        self.transform(&mut fb);
+        for (x, y) in fa.iter_mut().zip(fb) {
+            *x *= y;
+        }
+        self.inverse_transform(&mut fa);
+
+        fa.truncate(res_len);
+        fa
+    }
+
+    /// 数論変換の本体。`roots` に 1 の冪根の表を与えると、それを回転因子として変換する。
+    fn butterfly(&self, a: &mut [Modint<C>], roots: &[Modint<C>]) {
+        let n = a.len();
+        assert!(n.is_power_of_two(), "length must be a power of two");
+        assert!(
+            n <= self.max_len(),
+            "length {} exceeds the precomputed length {}",
+            n,
+            self.max_len()
+        );
+
+        // ビット反転順に並べ替える。長さ n でのビット反転は、長さ max_len でのビット反転を右に
+        // シフトしたものになる。
+        let shift = self.log - n.trailing_zeros();
+        for i in 0..n {
+            let j = self.rev[i] >> shift;
+            if i < j {
+                a.swap(i, j);
+            }
+        }
+
+        let mut len = 2;
+        while len <= n {
+            let half = len / 2;
+            let step = self.max_len() / len;
+            for i in (0..n).step_by(len) {
+                for k in 0..half {
+                    let u = a[i + k];
+                    let v = a[i + k + half] * roots[k * step];
+                    a[i + k] = u + v;
+                    a[i + k + half] = u - v;
+                }
+            }
+
+            len <<= 1;
+        }
+    }
+}
+
+/// 数論変換を行う。長さは 2 の冪でなければならない。
+///
+/// 何度も変換を行う場合は `NttContext` を使うと回転因子の計算を省ける。
+///
+/// # 計算量
+///
+/// O(n log n)
+pub fn ntt<C: ModintConst>(a: &mut [Modint<C>]) {
+    NttContext::new(a.len()).transform(a);
+}
+
+/// 数論変換の逆変換を行う。長さは 2 の冪でなければならない。
+///
+/// # 計算量
+///
+/// O(n log n)
+pub fn intt<C: ModintConst>(a: &mut [Modint<C>]) {
+    NttContext::new(a.len()).inverse_transform(a);
+}
+
+/// 二つの数列の畳み込みを求める。
+///
+/// 結果の長さは `a.len() + b.len() - 1` (どちらかが空なら空) 。
+///
+/// # 計算量
+///
+/// n = a.len() + b.len() として O(n log n)
+pub fn convolution<C: ModintConst>(a: &[Modint<C>], b: &[Modint<C>]) -> Vec<Modint<C>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    NttContext::new(a.len() + b.len() - 1).convolution(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    define_modint_const! {
+        pub const ModNtt = 998_244_353;
+    }
+
+    type M = Modint<ModNtt>;
+
+    fn naive(a: &[M], b: &[M]) -> Vec<M> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+
+        let mut res = vec![M::new(0); a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                res[i + j] += x * y;
+            }
+        }
+
+        res
+    }
+
+    #[test]
+    fn ntt_roundtrip() {
+        let orig: Vec<M> = (0..8).map(|x| M::new(x * x + 1)).collect();
+        let mut a = orig.clone();
+        ntt(&mut a);
+        intt(&mut a);
+        assert_eq!(a, orig);
+    }
+
+    #[test]
+    fn convolution_matches_naive() {
+        let a: Vec<M> = (0..37).map(|x| M::new(x * 12345 + 678)).collect();
+        let b: Vec<M> = (0..23).map(|x| M::new(998_244_352 - x * 31)).collect();
+        assert_eq!(convolution(&a, &b), naive(&a, &b));
+
+        let one = [M::new(7)];
+        assert_eq!(convolution(&one, &b), naive(&one, &b));
+        assert_eq!(convolution(&a, &[]), vec![]);
+    }
+
+    #[test]
+    fn ntt_context_reuse() {
+        let ctx = NttContext::<ModNtt>::new(100);
+        assert_eq!(ctx.max_len(), 128);
+
+        let a: Vec<M> = (0..37).map(|x| M::new(x * 12345 + 678)).collect();
+        let b: Vec<M> = (0..23).map(|x| M::new(998_244_352 - x * 31)).collect();
+        let c: Vec<M> = (0..5).map(|x| M::new(x + 1)).collect();
+        assert_eq!(ctx.convolution(&a, &b), convolution(&a, &b));
+        assert_eq!(ctx.convolution(&b, &c), convolution(&b, &c));
+        assert_eq!(ctx.convolution(&c, &c), naive(&c, &c));
+
+        // 前計算した長さより短い変換も、一回限りの変換と一致する。
+        let orig: Vec<M> = (0..16).map(|x| M::new(x * x + 1)).collect();
+        let mut x = orig.clone();
+        let mut y = orig.clone();
+        ctx.transform(&mut x);
+        ntt(&mut y);
+        assert_eq!(x, y);
+        ctx.inverse_transform(&mut x);
+        assert_eq!(x, orig);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ntt_context_too_long() {
+        let ctx = NttContext::<ModNtt>::new(4);
+        ctx.convolution(&[M::new(1); 3], &[M::new(1); 3]);
+    }
+}
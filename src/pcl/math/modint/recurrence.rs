@@ -0,0 +1,186 @@
+//! 線形漸化式を扱う関数を定義する。
+//!
+//! 線形漸化式 a_n = c_1 a_{n-1} + c_2 a_{n-2} + ... + c_d a_{n-d} の係数は `[c_1, c_2, ..., c_d]`
+//! のように並べて表す。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::modint::Modint17 as M;
+//! # use procon_lib::pcl::math::modint::recurrence::{berlekamp_massey, kth_term};
+//! // use crate::pcl::math::modint::Modint17 as M;
+//! // use crate::pcl::math::modint::recurrence::{berlekamp_massey, kth_term};
+//! // フィボナッチ数列の最初の数項から漸化式を復元する。
+//! let fib: Vec<_> = [0, 1, 1, 2, 3, 5, 8, 13].iter().map(|&x| M::new(x)).collect();
+//! let rec = berlekamp_massey(&fib);
+//! assert_eq!(rec, vec![M::new(1), M::new(1)]);
+//! assert_eq!(kth_term(&rec, &fib, 90), M::new(2_880_067_194_370_816_120 % 1_000_000_007));
+//! ```
+
+use super::consts::ModintConst;
+use super::Modint;
+
+/// 数列を生成する最短の線形漸化式の係数を求める (Berlekamp–Massey 法) 。
+///
+/// 漸化式の次数を d とすると、長さ 2d 以上の数列を与えれば正しい漸化式が求まる。すべて 0 の数列に対
+/// しては空の係数列を返す。
+///
+/// # 計算量
+///
+/// O(n^2)
+pub fn berlekamp_massey<C: ModintConst>(seq: &[Modint<C>]) -> Vec<Modint<C>> {
+    let zero = Modint::new(0);
+    // 現在の接続多項式 (定数項は 1) と、最後に次数が上がる直前の接続多項式
+    let mut cur = vec![Modint::new(1)];
+    let mut prev = vec![Modint::new(1)];
+    let mut len = 0;
+    // prev を求めたときの不一致の値と、そこから進んだ項の数
+    let mut prev_diff = Modint::new(1);
+    let mut shift = 1;
+
+    for n in 0..seq.len() {
+        let mut diff = seq[n];
+        for i in 1..=len {
+            diff += cur[i] * seq[n - i];
+        }
+
+        if diff == zero {
+            shift += 1;
+            continue;
+        }
+
+        let coef = diff / prev_diff;
+        let old = cur.clone();
+        if cur.len() < prev.len() + shift {
+            cur.resize(prev.len() + shift, zero);
+        }
+        for (i, &p) in prev.iter().enumerate() {
+            cur[i + shift] -= coef * p;
+        }
+
+        if 2 * len <= n {
+            len = n + 1 - len;
+            prev = old;
+            prev_diff = diff;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+
+    cur.resize(len + 1, zero);
+    cur[1..].iter().map(|&c| -c).collect()
+}
+
+/// 漸化式の係数 `rec` と初項 `init` で定まる数列の第 `k` 項 (0-indexed) を求める (Kitamasa 法) 。
+///
+/// `init` の最初の `rec.len()` 項を初項として用いる。`rec` が空の場合は常に 0 を返す。
+///
+/// # 計算量
+///
+/// 漸化式の次数を d として O(d^2 log k)
+pub fn kth_term<C: ModintConst>(rec: &[Modint<C>], init: &[Modint<C>], k: u64) -> Modint<C> {
+    let d = rec.len();
+    assert!(
+        init.len() >= d,
+        "need at least {} initial terms but {} given",
+        d,
+        init.len()
+    );
+
+    // x^k を特性多項式で割った余りを求めると、その係数が初項にかかる係数になる。
+    let mut res = reduce(vec![Modint::new(1)], rec);
+    let mut base = reduce(vec![Modint::new(0), Modint::new(1)], rec);
+    let mut k = k;
+    while k > 0 {
+        if k & 1 != 0 {
+            res = mul_mod(&res, &base, rec);
+        }
+        base = mul_mod(&base, &base, rec);
+        k >>= 1;
+    }
+
+    res.iter().zip(init).map(|(&r, &a)| r * a).sum()
+}
+
+/// 多項式 `p` を特性多項式 x^d - c_1 x^{d-1} - ... - c_d で割った余りを、長さ d の係数列で返す。
+fn reduce<C: ModintConst>(mut p: Vec<Modint<C>>, rec: &[Modint<C>]) -> Vec<Modint<C>> {
+    let d = rec.len();
+    for i in (d..p.len()).rev() {
+        let coef = p[i];
+        for (j, &c) in rec.iter().enumerate() {
+            p[i - j - 1] += coef * c;
+        }
+    }
+    p.resize(d, Modint::new(0));
+    p
+}
+
+/// 二つの多項式の積を特性多項式で割った余りを求める。
+fn mul_mod<C: ModintConst>(a: &[Modint<C>], b: &[Modint<C>], rec: &[Modint<C>]) -> Vec<Modint<C>> {
+    if a.is_empty() || b.is_empty() {
+        return vec![Modint::new(0); rec.len()];
+    }
+
+    let mut prod = vec![Modint::new(0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            prod[i + j] += x * y;
+        }
+    }
+
+    reduce(prod, rec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::math::modint::Modint17 as M;
+
+    fn naive(rec: &[M], init: &[M], n: usize) -> Vec<M> {
+        let mut seq = init[..rec.len()].to_vec();
+        while seq.len() < n {
+            let k = seq.len();
+            let next = rec
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| c * seq[k - i - 1])
+                .sum();
+            seq.push(next);
+        }
+        seq.truncate(n);
+        seq
+    }
+
+    #[test]
+    fn recurrence_fibonacci() {
+        let fib = naive(&[M::new(1), M::new(1)], &[M::new(0), M::new(1)], 10);
+        let rec = berlekamp_massey(&fib);
+        assert_eq!(rec, vec![M::new(1), M::new(1)]);
+        assert_eq!(kth_term(&rec, &fib, 0), M::new(0));
+        assert_eq!(kth_term(&rec, &fib, 9), fib[9]);
+        assert_eq!(
+            kth_term(&rec, &fib, 1_000_000_000_000_000_000),
+            M::new(209_783_453)
+        );
+    }
+
+    #[test]
+    fn recurrence_recover() {
+        let rec = vec![M::new(2), M::new(0), M::new(-3), M::new(5)];
+        let init = vec![M::new(1), M::new(-4), M::new(9), M::new(16)];
+        let seq = naive(&rec, &init, 40);
+        assert_eq!(berlekamp_massey(&seq[..8]), rec);
+        for (k, &x) in seq.iter().enumerate() {
+            assert_eq!(kth_term(&rec, &init, k as u64), x);
+        }
+
+        // 等比数列は次数 1
+        let geo = naive(&[M::new(3)], &[M::new(2)], 10);
+        assert_eq!(berlekamp_massey(&geo), vec![M::new(3)]);
+        assert_eq!(kth_term(&[M::new(3)], &[M::new(2)], 9), geo[9]);
+
+        assert!(berlekamp_massey(&[M::new(0); 5]).is_empty());
+        assert_eq!(kth_term(&[], &[], 100), M::new(0));
+    }
+}
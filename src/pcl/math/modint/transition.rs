@@ -0,0 +1,151 @@
+//! 確率分布や場合の数の線形な遷移を扱う関数を定義する。
+//!
+//! 「状態 i から確率 p で状態 j に移る」という遷移を何回も繰り返した後の分布を求めるのは、期待値や
+//! 確率の DP でよく現れる。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::math::modint::Modint998244353 as M;
+//! # use procon_lib::pcl::math::modint::transition::linear_transition;
+//! // use crate::pcl::math::modint::Modint998244353 as M;
+//! // use crate::pcl::math::modint::transition::linear_transition;
+//! // コインを投げて表なら 1 に、裏なら 0 にいる。
+//! let half = M::new(2).inv();
+//! let trans = [(0, 0, half), (0, 1, half), (1, 0, half), (1, 1, half)];
+//! let dist = linear_transition(2, &trans, &[M::new(1), M::new(0)], 3);
+//! assert_eq!(dist, vec![half, half]);
+//! ```
+
+use super::consts::ModintConst;
+use super::Modint;
+use crate::pcl::math::matrix::Matrix;
+
+/// 初期分布 `init` に疎な遷移 `transitions` を `steps` 回適用した後の分布を求める。
+///
+/// `transitions` の各要素 `(from, to, p)` は、状態 `from` の値の `p` 倍が状態 `to` に加えられるこ
+/// とを表す。同じ `(from, to)` の組が複数あればその和をとる。
+///
+/// `steps` が小さければ遷移をそのまま繰り返し、大きければ遷移行列の累乗を使う。
+///
+/// # 計算量
+///
+/// 状態数を S 、遷移の数を E として O(min(steps (S + E), S^3 log steps))
+pub fn linear_transition<C: ModintConst>(
+    states: usize,
+    transitions: &[(usize, usize, Modint<C>)],
+    init: &[Modint<C>],
+    steps: u64,
+) -> Vec<Modint<C>> {
+    assert_eq!(
+        init.len(),
+        states,
+        "the length of init does not match the number of states"
+    );
+    for &(from, to, _) in transitions {
+        assert!(
+            from < states && to < states,
+            "invalid transition: {} -> {} but states is {}",
+            from,
+            to,
+            states
+        );
+    }
+
+    let sparse_cost = (states + transitions.len()) as u128 * steps as u128;
+    let log_steps = 64 - u128::from(steps.leading_zeros());
+    let matrix_cost = (states as u128).pow(3) * log_steps;
+    if sparse_cost <= matrix_cost {
+        let mut cur = init.to_vec();
+        for _ in 0..steps {
+            let mut next = vec![Modint::new(0); states];
+            for &(from, to, p) in transitions {
+                next[to] += cur[from] * p;
+            }
+            cur = next;
+        }
+
+        return cur;
+    }
+
+    // 分布を行ベクトルとみなし、右から遷移行列の累乗を掛ける。
+    let mut mat = Matrix::zeros(states, states);
+    for &(from, to, p) in transitions {
+        mat[(from, to)] += p;
+    }
+    let mut row = Matrix::zeros(1, states);
+    for (i, &x) in init.iter().enumerate() {
+        row[(0, i)] = x;
+    }
+
+    row.mul(&mat.pow(steps)).row(0).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    define_modint_const! {
+        pub const ModTrans = 998_244_353;
+    }
+
+    type M = Modint<ModTrans>;
+
+    fn step(states: usize, transitions: &[(usize, usize, M)], cur: &[M]) -> Vec<M> {
+        let mut next = vec![M::new(0); states];
+        for &(from, to, p) in transitions {
+            next[to] += cur[from] * p;
+        }
+
+        next
+    }
+
+    #[test]
+    fn linear_transition_markov_chain() {
+        // 0 -> {0, 1, 2} に 1/3 ずつ、1 -> 2 、2 -> {0, 2} に 1/2 ずつ
+        let third = M::new(3).inv();
+        let half = M::new(2).inv();
+        let trans = [
+            (0, 0, third),
+            (0, 1, third),
+            (0, 2, third),
+            (1, 2, M::new(1)),
+            (2, 0, half),
+            (2, 2, half),
+        ];
+        let init = [M::new(1), M::new(0), M::new(0)];
+
+        let mut expected = init.to_vec();
+        for steps in 0..6 {
+            assert_eq!(linear_transition(3, &trans, &init, steps), expected);
+            expected = step(3, &trans, &expected);
+        }
+
+        // 行列累乗を使う場合も、遷移を繰り返した結果と一致する。
+        let mut expected = init.to_vec();
+        for _ in 0..1000 {
+            expected = step(3, &trans, &expected);
+        }
+        assert_eq!(linear_transition(3, &trans, &init, 1000), expected);
+
+        // 確率の和は 1 のまま
+        let total = linear_transition(3, &trans, &init, 1_000_000_000_000)
+            .into_iter()
+            .fold(M::new(0), |acc, x| acc + x);
+        assert_eq!(total, M::new(1));
+    }
+
+    #[test]
+    fn linear_transition_counts_paths() {
+        // 完全グラフ K_3 上で頂点 0 から始まり 0 で終わる長さ k の歩道は (2^k + 2 (-1)^k) / 3 個
+        let trans: Vec<_> = (0..3)
+            .flat_map(|i| {
+                (0..3)
+                    .filter(move |&j| j != i)
+                    .map(move |j| (i, j, M::new(1)))
+            })
+            .collect();
+        let dist = linear_transition(3, &trans, &[M::new(1), M::new(0), M::new(0)], 10);
+        assert_eq!(dist, vec![M::new(342), M::new(341), M::new(341)]);
+    }
+}
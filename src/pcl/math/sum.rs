@@ -1,4 +1,5 @@
-//! 区間の和を高速に計算する `CumSum`, `CumSum2D` を定義する。
+//! 区間の和を高速に計算する `CumSum`, `CumSum2D` と、区間への加算をまとめて行う `Imos`, `Imos2D` を
+//! 定義する。
 //!
 //! # `CumSum`
 //!
@@ -42,9 +43,47 @@
 //! assert_eq!(cumsum2d.sum(3..2, 3..4).0, 0);
 //! assert_eq!(cumsum2d.sum(1..2, 4..3).0, 0);
 //! ```
+//!
+//! # `Imos`
+//!
+//! 区間への加算をいくつも行った後の数列を、いもす法 (差分配列) でまとめて求める。
+//!
+//! ```
+//! # use procon_lib::pcl::math::Imos;
+//! # use procon_lib::pcl::traits::math::group::Additive as A;
+//! // use crate::pcl::math::Imos;
+//! // use crate::pcl::math::group::Additive as A;
+//! let mut imos = Imos::new(5);
+//! imos.add(1..4, A(2));
+//! imos.add(..2, A(10));
+//! imos.add(3.., A(-1));
+//! let a: Vec<_> = imos.build().into_iter().map(|x| x.0).collect();
+//! assert_eq!(a, vec![10, 12, 2, 1, -1]);
+//! ```
+//!
+//! # `Imos2D`
+//!
+//! 長方形の範囲への加算をいくつも行った後の行列を、二次元のいもす法でまとめて求める。
+//!
+//! ```
+//! # use procon_lib::pcl::math::Imos2D;
+//! # use procon_lib::pcl::traits::math::group::Additive as A;
+//! // use crate::pcl::math::Imos2D;
+//! // use crate::pcl::math::group::Additive as A;
+//! let mut imos = Imos2D::new(3, 4);
+//! imos.add(0..2, 1..3, A(1));
+//! imos.add(1.., ..2, A(10));
+//! let a: Vec<Vec<_>> = imos
+//!     .build()
+//!     .into_iter()
+//!     .map(|row| row.into_iter().map(|x| x.0).collect())
+//!     .collect();
+//! assert_eq!(a, vec![vec![0, 1, 1, 0], vec![10, 11, 1, 0], vec![10, 10, 0, 0]]);
+//! ```
 
 use crate::pcl::traits::Group;
 use crate::pcl::utils::range;
+use std::iter::FromIterator;
 use std::ops::RangeBounds;
 
 /// ある数列の、指定された範囲の和を高速に計算する。
@@ -103,6 +142,36 @@ impl<T: Group + Copy> CumSum<T> {
     }
 }
 
+impl<T: Group + Copy> FromIterator<T> for CumSum<T> {
+    /// イテレータの要素を順に読みながら累積和をとり、 `CumSum` を生成する。いったん `Vec` に集め
+    /// る必要はない。
+    ///
+    /// ```
+    /// # use procon_lib::pcl::math::CumSum;
+    /// # use procon_lib::pcl::traits::math::group::Additive as A;
+    /// // use crate::pcl::math::CumSum;
+    /// // use crate::pcl::math::group::Additive as A;
+    /// let cumsum: CumSum<_> = (1..=6).map(|x| A(x * x)).collect();
+    /// assert_eq!(cumsum.sum(1..3).0, 13);
+    /// ```
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> CumSum<T> {
+        let iter = iter.into_iter();
+        let mut psum = Vec::with_capacity(iter.size_hint().0 + 1);
+        let mut acc = T::id();
+        psum.push(acc);
+        for x in iter {
+            acc = T::op(acc, x);
+            psum.push(acc);
+        }
+
+        CumSum { psum }
+    }
+}
+
 /// ある二次元数列の、指定された範囲の和を高速に計算する。
 ///
 /// 実際は必ずしも通常の整数と和である必要はなく、群 (`Group`) であれば良い。
@@ -156,6 +225,41 @@ impl<T: Group + Copy> CumSum2D<T> {
         CumSum2D { psum }
     }
 
+    /// 行優先で一列に並べられた `height` 行 `width` 列の行列の累積和をとり、 `CumSum2D` を生成す
+    /// る。
+    ///
+    /// `data[i * width + j]` が `i` 行 `j` 列の要素となる。`data.len() == height * width` でなけ
+    /// ればならない。
+    ///
+    /// # 計算量
+    ///
+    /// O(height * width)
+    pub fn from_flat(data: &[T], height: usize, width: usize) -> CumSum2D<T> {
+        assert_eq!(
+            data.len(),
+            height * width,
+            "the data's length does not match height * width"
+        );
+
+        let mut psum = vec![vec![T::id(); width + 1]; height + 1];
+        // 古い Rust をサポートするため、 1..=height は利用しない。
+        for i in 0..height {
+            let i = i + 1;
+            for j in 0..width {
+                let j = j + 1;
+                psum[i][j] = T::op(
+                    T::op(
+                        T::op(psum[i - 1][j], psum[i][j - 1]),
+                        T::inv(psum[i - 1][j - 1]),
+                    ),
+                    data[(i - 1) * width + (j - 1)],
+                )
+            }
+        }
+
+        CumSum2D { psum }
+    }
+
     /// 指定された範囲内の総和を返す。
     ///
     /// # 計算量
@@ -201,9 +305,146 @@ impl<T: Group + Copy> CumSum2D<T> {
     }
 }
 
+/// 区間への加算をまとめて行った結果を、いもす法で求める。
+///
+/// 実際は必ずしも通常の整数と和である必要はなく、群 (`Group`) であれば良い。
+pub struct Imos<T> {
+    diff: Vec<T>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<T: Group + Copy> Imos<T> {
+    /// すべての要素が単位元である長さ `n` の数列を表す `Imos` を生成する。
+    pub fn new(n: usize) -> Imos<T> {
+        Imos {
+            diff: vec![T::id(); n + 1],
+        }
+    }
+
+    /// 指定された範囲の各要素に `value` を加える。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, value: T) {
+        let start = range::range_start(&range, 0);
+        let end = range::range_end(&range, self.len());
+        if end <= start {
+            return;
+        }
+
+        self.diff[start] = T::op(self.diff[start], value);
+        self.diff[end] = T::op(self.diff[end], T::inv(value));
+    }
+
+    /// これまでの加算をすべて反映した数列を求める。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn build(&self) -> Vec<T> {
+        let mut res = Vec::with_capacity(self.len());
+        let mut acc = T::id();
+        for &d in &self.diff[..self.len()] {
+            acc = T::op(acc, d);
+            res.push(acc);
+        }
+
+        res
+    }
+
+    /// 数列の長さを取得する。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.diff.len() - 1
+    }
+}
+
+/// 長方形の範囲への加算をまとめて行った結果を、二次元のいもす法で求める。
+///
+/// 実際は必ずしも通常の整数と和である必要はなく、群 (`Group`) であれば良い。
+pub struct Imos2D<T> {
+    diff: Vec<Vec<T>>,
+}
+
+impl<T: Group + Copy> Imos2D<T> {
+    /// すべての要素が単位元である `height` 行 `width` 列の行列を表す `Imos2D` を生成する。
+    pub fn new(height: usize, width: usize) -> Imos2D<T> {
+        Imos2D {
+            diff: vec![vec![T::id(); width + 1]; height + 1],
+        }
+    }
+
+    /// 指定された範囲の各要素に `value` を加える。範囲が空であれば何もしない。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn add<RY, RX>(&mut self, yrange: RY, xrange: RX, value: T)
+    where
+        RY: RangeBounds<usize>,
+        RX: RangeBounds<usize>,
+    {
+        let (height, width) = self.size();
+        let ystart = range::range_start(&yrange, 0);
+        let yend = range::range_end(&yrange, height);
+        let xstart = range::range_start(&xrange, 0);
+        let xend = range::range_end(&xrange, width);
+        if yend <= ystart || xend <= xstart {
+            return;
+        }
+
+        let inv = T::inv(value);
+        self.diff[ystart][xstart] = T::op(self.diff[ystart][xstart], value);
+        self.diff[ystart][xend] = T::op(self.diff[ystart][xend], inv);
+        self.diff[yend][xstart] = T::op(self.diff[yend][xstart], inv);
+        self.diff[yend][xend] = T::op(self.diff[yend][xend], value);
+    }
+
+    /// これまでの加算をすべて反映した行列を求める。
+    ///
+    /// # 計算量
+    ///
+    /// O(height * width)
+    pub fn build(&self) -> Vec<Vec<T>> {
+        let (height, width) = self.size();
+        let mut res: Vec<Vec<T>> = Vec::with_capacity(height);
+        for i in 0..height {
+            // 横方向に累積してから、一つ上の行の結果を足して縦方向にも累積する。
+            let mut row = Vec::with_capacity(width);
+            let mut acc = T::id();
+            for j in 0..width {
+                acc = T::op(acc, self.diff[i][j]);
+                row.push(match res.last() {
+                    Some(prev) => T::op(prev[j], acc),
+                    None => acc,
+                });
+            }
+            res.push(row);
+        }
+
+        res
+    }
+
+    /// 行列の大きさを取得する。
+    ///
+    /// 戻り値は (高さ, 幅)
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn size(&self) -> (usize, usize) {
+        (self.diff.len() - 1, self.diff[0].len() - 1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pcl::test_util::XorShift;
     use crate::pcl::traits::math::group::Additive as A;
 
     #[test]
@@ -224,6 +465,25 @@ mod tests {
         assert_eq!(cumsum.len(), 6);
     }
 
+    #[test]
+    fn check_cumsum_from_iter() {
+        let array: Vec<_> = (1..=6).map(A).collect();
+        let from_array = CumSum::from_array(&array);
+        let from_iter = CumSum::from_iter((1..=6).map(A));
+        assert_eq!(from_iter.len(), 6);
+        for l in 0..=6 {
+            for r in l..=6 {
+                assert_eq!(from_iter.sum(l..r).0, from_array.sum(l..r).0);
+            }
+        }
+
+        // 長さのわからないイテレータからでもよい。
+        let cumsum: CumSum<_> = (1..100).filter(|x| x % 7 == 3).map(A).collect();
+        assert_eq!(cumsum.len(), 14);
+        assert_eq!(cumsum.sum(..).0, (0..14).map(|k| 7 * k + 3).sum::<i32>());
+        assert_eq!(CumSum::from_iter(Vec::<A<i64>>::new()).sum(..).0, 0);
+    }
+
     #[test]
     #[allow(clippy::reversed_empty_ranges)]
     fn check_cumsum2d() {
@@ -241,4 +501,110 @@ mod tests {
 
         assert_eq!(cumsum2d.size(), (4, 5));
     }
+
+    #[test]
+    fn check_cumsum2d_from_flat() {
+        let matrix = vec![
+            vec![A(4), A(2), A(3), A(6), A(1)],
+            vec![A(5), A(5), A(2), A(1), A(4)],
+            vec![A(1), A(2), A(3), A(2), A(2)],
+            vec![A(3), A(2), A(1), A(3), A(2)],
+        ];
+        let flat: Vec<_> = matrix.iter().flat_map(|row| row.iter().cloned()).collect();
+        let nested = CumSum2D::from_matrix(&matrix);
+        let cumsum2d = CumSum2D::from_flat(&flat, 4, 5);
+
+        assert_eq!(cumsum2d.size(), (4, 5));
+        for y1 in 0..=4 {
+            for y2 in y1..=4 {
+                for x1 in 0..=5 {
+                    for x2 in x1..=5 {
+                        assert_eq!(cumsum2d.sum(y1..y2, x1..x2).0, nested.sum(y1..y2, x1..x2).0);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(CumSum2D::<A<i32>>::from_flat(&[], 0, 3).size(), (0, 3));
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn check_imos() {
+        let n = 8;
+        let ranges = [(0, 3, 5), (2, 6, -2), (1, 8, 1), (5, 5, 100), (4, 7, 3)];
+        let mut imos = Imos::new(n);
+        let mut naive = vec![0; n];
+        for &(l, r, x) in &ranges {
+            imos.add(l..r, A(x));
+            for v in &mut naive[l..r] {
+                *v += x;
+            }
+        }
+        imos.add(6..3, A(1000));
+        imos.add(..=1, A(-7));
+        naive[0] -= 7;
+        naive[1] -= 7;
+
+        let built: Vec<i64> = imos.build().into_iter().map(|x| x.0).collect();
+        assert_eq!(built, naive);
+        assert_eq!(imos.len(), n);
+        assert!(Imos::<A<i64>>::new(0).build().is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn check_imos2d() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for &(h, w) in &[(1, 1), (3, 5), (6, 2), (7, 7)] {
+            let mut imos = Imos2D::new(h, w);
+            let mut naive = vec![vec![0i64; w]; h];
+            for _ in 0..20 {
+                let (y1, y2) = (
+                    (rng.next_u64() % (h as u64 + 1)) as usize,
+                    (rng.next_u64() % (h as u64 + 1)) as usize,
+                );
+                let (x1, x2) = (
+                    (rng.next_u64() % (w as u64 + 1)) as usize,
+                    (rng.next_u64() % (w as u64 + 1)) as usize,
+                );
+                let x = (rng.next_u64() % 21) as i64 - 10;
+                // 逆向きの範囲は空として扱われる。
+                imos.add(y1..y2, x1..x2, A(x));
+                for row in naive.iter_mut().take(y2).skip(y1) {
+                    for v in row.iter_mut().take(x2).skip(x1) {
+                        *v += x;
+                    }
+                }
+            }
+            imos.add(.., 1.., A(3));
+            for row in &mut naive {
+                for v in &mut row[1..] {
+                    *v += 3;
+                }
+            }
+
+            let built: Vec<Vec<i64>> = imos
+                .build()
+                .into_iter()
+                .map(|row| row.into_iter().map(|x| x.0).collect())
+                .collect();
+            assert_eq!(built, naive);
+            assert_eq!(imos.size(), (h, w));
+        }
+
+        let mut empty = Imos2D::new(0, 4);
+        empty.add(.., .., A(1));
+        assert!(empty.build().is_empty());
+        let built = Imos2D::<A<i32>>::new(2, 0).build();
+        assert_eq!(built.len(), 2);
+        assert!(built.iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_cumsum2d_from_flat_length_mismatch() {
+        CumSum2D::from_flat(&[A(1), A(2), A(3)], 2, 2);
+    }
 }
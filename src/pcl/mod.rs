@@ -10,5 +10,7 @@ pub mod macros;
 pub mod math;
 pub mod prelude;
 pub mod structure;
+#[cfg(test)]
+pub(crate) mod test_util;
 pub mod traits;
 pub mod utils;
@@ -0,0 +1,644 @@
+//! 標準入力などから空白区切りのトークンを読み込む関数と、出力をまとめて書き出す `Writer` を定義す
+//! る。
+//!
+//! proconio が使えない環境向けの最小限の入力ルーチン。`_from` で終わる関数は任意の `Read` から読み
+//! 込み、そうでないものは標準入力から読み込む。大きな入力を読み込むときは `Scanner` を使う。
+//!
+//! # インタラクティブな問題
+//!
+//! `Scanner` は入力を最後まで読み込もうとするので使えない。`read_token()` や `read_line()` は必要
+//! な分しか読み込まないので、ジャッジの応答を一つずつ読める。質問を出力したら、応答を読む前に必ず
+//! `flush()` すること。
+//!
+//! ```no_run
+//! # use procon_lib::pcl::polyfill::io::{flush, read, read_line};
+//! // use crate::pcl::polyfill::io::{flush, read, read_line};
+//! let n: u32 = read();
+//! let (mut lo, mut hi) = (1, n + 1);
+//! while hi - lo > 1 {
+//!     let mid = (lo + hi) / 2;
+//!     println!("? {}", mid);
+//!     flush();
+//!     if read_line() == "Yes" {
+//!         lo = mid;
+//!     } else {
+//!         hi = mid;
+//!     }
+//! }
+//! println!("! {}", lo);
+//! ```
+//!
+//! ```
+//! # use procon_lib::pcl::polyfill::io::{read_from, read_token_from};
+//! // use crate::pcl::polyfill::io::{read_from, read_token_from};
+//! use std::io::Cursor;
+//! let mut input = Cursor::new("3 abc\n-5");
+//! assert_eq!(read_from::<_, usize>(&mut input), 3);
+//! assert_eq!(read_token_from(&mut input), "abc");
+//! assert_eq!(read_from::<_, i32>(&mut input), -5);
+//! ```
+
+use crate::pcl::structure::graph::EdgeList;
+use crate::pcl::traits::math::graph::{Edge, Graph};
+use std::fmt::Display;
+use std::io::{self, BufWriter, Read, Stdout, Write};
+use std::str::FromStr;
+
+/// 空白で区切られたトークンを一つ読み込む。
+///
+/// 先頭の空白は読み飛ばす。トークンの直後の空白一文字までしか読み込まないので、同じ `Read` から続
+/// けて読み込むことができる。何も読み込めないまま入力が終わった場合は空文字列を返す。
+// 必要以上に先読みしないよう、あえてバッファリングせずに一バイトずつ読む。
+#[allow(unknown_lints, clippy::unbuffered_bytes)]
+pub fn read_token_from<R: Read>(read: R) -> String {
+    let token: Vec<u8> = read
+        .bytes()
+        .map(|b| b.expect("failed to read from input"))
+        .skip_while(|b| b.is_ascii_whitespace())
+        .take_while(|b| !b.is_ascii_whitespace())
+        .collect();
+
+    String::from_utf8(token).expect("input is not valid UTF-8")
+}
+
+/// 空白で区切られたトークンを一つ読み込み、`T` として解釈する。解釈に失敗すると panic する。
+pub fn read_from<R: Read, T: FromStr>(read: R) -> T {
+    let token = read_token_from(read);
+    match token.parse() {
+        Ok(value) => value,
+        Err(_) => panic!("failed to parse token: {:?}", token),
+    }
+}
+
+/// 空白で区切られたトークンを一つ読み込み、文字の配列として返す。
+pub fn read_chars_from<R: Read>(read: R) -> Vec<char> {
+    read_token_from(read).chars().collect()
+}
+
+/// 空白で区切られたトークンを一つ読み込み、バイト列として返す。
+pub fn read_bytes_from<R: Read>(read: R) -> Vec<u8> {
+    read_token_from(read).into_bytes()
+}
+
+/// 一行読み込み、末尾の改行 (`\n` または `\r\n`) を取り除いて返す。
+///
+/// 改行までしか読み込まないので、インタラクティブな問題でも使える。入力が終わっていれば空文字列を
+/// 返す。
+// 次の行まで先読みしないよう、あえてバッファリングせずに一バイトずつ読む。
+#[allow(unknown_lints, clippy::unbuffered_bytes)]
+pub fn read_line_from<R: Read>(read: R) -> String {
+    let mut line: Vec<u8> = read
+        .bytes()
+        .map(|b| b.expect("failed to read from input"))
+        .take_while(|&b| b != b'\n')
+        .collect();
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+
+    String::from_utf8(line).expect("input is not valid UTF-8")
+}
+
+/// 空白で区切られたトークンを `n` 個読み込み、それぞれ `T` として解釈する。
+pub fn read_vec_from<R: Read, T: FromStr>(mut read: R, n: usize) -> Vec<T> {
+    (0..n).map(|_| read_from(&mut read)).collect()
+}
+
+/// 空白で区切られたトークンを `h * w` 個読み込み、`h` 行 `w` 列の行列として返す。
+///
+/// 行の区切りは改行でなくてもよく、単に先頭から `w` 個ずつを一行とする。
+pub fn read_matrix_from<R: Read, T: FromStr>(mut read: R, h: usize, w: usize) -> Vec<Vec<T>> {
+    (0..h).map(|_| read_vec_from(&mut read, w)).collect()
+}
+
+/// 入力を最後まで読み込み、空白または `delim` で区切られた各フィールドを `T` として解釈する。
+///
+/// 空のフィールドは読み飛ばす。カンマ区切りのテストデータなどを読み込むときに使う。解釈に失敗する
+/// と panic する。
+pub fn read_delimited_from<R: Read, T: FromStr>(mut read: R, delim: char) -> Vec<T> {
+    let mut input = String::new();
+    read.read_to_string(&mut input)
+        .expect("failed to read from input");
+
+    input
+        .split(|c: char| c == delim || c.is_whitespace())
+        .filter(|field| !field.is_empty())
+        .map(|field| match field.parse() {
+            Ok(value) => value,
+            Err(_) => panic!("failed to parse field: {:?}", field),
+        })
+        .collect()
+}
+
+/// 重み付きの辺 `u v w` を `m` 本読み込み、辺リストとして返す。
+///
+/// `one_indexed` が true なら頂点番号を 1 引いて 0-indexed にする。頂点数は辺に現れる頂点番号の最
+/// 大値に 1 を足したものになるので、孤立点を含めたい場合は必要に応じて作り直すこと。
+///
+/// ```
+/// # use procon_lib::pcl::polyfill::io::read_weighted_edges_from;
+/// # use procon_lib::pcl::traits::math::graph::ReadonlyGraph;
+/// // use crate::pcl::polyfill::io::read_weighted_edges_from;
+/// // use crate::pcl::traits::math::graph::ReadonlyGraph;
+/// use std::io::Cursor;
+/// let graph = read_weighted_edges_from::<_, i64>(Cursor::new("1 2 5\n2 3 -1\n"), 2, true);
+/// assert_eq!(graph.size(), 3);
+/// assert_eq!(graph.edges()[1].cost, -1);
+/// ```
+pub fn read_weighted_edges_from<R: Read, C: FromStr>(
+    mut read: R,
+    m: usize,
+    one_indexed: bool,
+) -> EdgeList<C> {
+    let offset = if one_indexed { 1 } else { 0 };
+    let mut edges = Vec::with_capacity(m);
+    for _ in 0..m {
+        let u: usize = read_from(&mut read);
+        let v: usize = read_from(&mut read);
+        let w: C = read_from(&mut read);
+        assert!(
+            u >= offset && v >= offset,
+            "vertex index must be 1-indexed but got {} {}",
+            u,
+            v
+        );
+        edges.push(Edge::new(u - offset, v - offset, w));
+    }
+
+    let n = edges
+        .iter()
+        .map(|e| e.from.max(e.to) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut graph = EdgeList::of_size(n);
+    graph.add_edges(edges);
+    graph
+}
+
+/// 入力全体を最初に一度だけ読み込み、そこから空白区切りのトークンを切り出すスキャナ。
+///
+/// `read_token_from()` などは一バイトずつ読み込むので、トークンが数十万個あるような大きな入力では
+/// 遅い。そのような場合はこちらを使う。入力を最後まで読み込んでしまうので、対話形式の問題には使え
+/// ない。
+///
+/// ```
+/// # use procon_lib::pcl::polyfill::io::Scanner;
+/// // use crate::pcl::polyfill::io::Scanner;
+/// use std::io::Cursor;
+/// let mut sc = Scanner::new(Cursor::new("3\n1 2 3\nabc"));
+/// let n: usize = sc.next();
+/// assert_eq!(sc.next_vec::<i64>(n), vec![1, 2, 3]);
+/// assert_eq!(sc.next_token(), "abc");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl Scanner {
+    /// `read` を最後まで読み込んでスキャナを生成する。
+    pub fn new<R: Read>(mut read: R) -> Scanner {
+        let mut buf = Vec::new();
+        read.read_to_end(&mut buf)
+            .expect("failed to read from input");
+
+        Scanner { buf, pos: 0 }
+    }
+
+    /// 標準入力を最後まで読み込んでスキャナを生成する。
+    pub fn stdin() -> Scanner {
+        let stdin = io::stdin();
+        let lock = stdin.lock();
+        Scanner::new(lock)
+    }
+
+    /// 空白で区切られたトークンを一つ返す。入力が残っていなければ空文字列を返す。
+    pub fn next_token(&mut self) -> &str {
+        let buf = &self.buf;
+        let start = self.pos
+            + buf[self.pos..]
+                .iter()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count();
+        let end = start
+            + buf[start..]
+                .iter()
+                .take_while(|b| !b.is_ascii_whitespace())
+                .count();
+        self.pos = end;
+
+        std::str::from_utf8(&buf[start..end]).expect("input is not valid UTF-8")
+    }
+
+    /// 空白で区切られたトークンを一つ読み、`T` として解釈する。解釈に失敗すると panic する。
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: FromStr>(&mut self) -> T {
+        let token = self.next_token();
+        match token.parse() {
+            Ok(value) => value,
+            Err(_) => panic!("failed to parse token: {:?}", token),
+        }
+    }
+
+    /// 空白で区切られたトークンを `n` 個読み、それぞれ `T` として解釈する。
+    pub fn next_vec<T: FromStr>(&mut self, n: usize) -> Vec<T> {
+        (0..n).map(|_| self.next()).collect()
+    }
+}
+
+/// 出力をバッファに溜めてまとめて書き出すライタ。
+///
+/// `println!` は一行ごとに書き出すので、大量の行を出力すると遅い。こちらはバッファが一杯になるか、
+/// 破棄されるときにまとめて書き出す。
+///
+/// ```
+/// # use procon_lib::pcl::polyfill::io::Writer;
+/// // use crate::pcl::polyfill::io::Writer;
+/// let mut out = Vec::new();
+/// {
+///     let mut writer = Writer::new(&mut out);
+///     writer.println(3);
+///     writer.print_iter(&[1, 2, 3], " ");
+/// }
+/// assert_eq!(out, b"3\n1 2 3\n");
+/// ```
+pub struct Writer<W: Write> {
+    inner: BufWriter<W>,
+}
+
+impl Writer<Stdout> {
+    /// 標準出力に書き出すライタを生成する。
+    ///
+    /// 古い Rust では `'static` な `StdoutLock` を作れないので `Stdout` をそのまま包むが、実際に書
+    /// き出すのはバッファが一杯になったときだけなので、ロックの回数は問題にならない。
+    pub fn stdout() -> Writer<Stdout> {
+        Writer::new(io::stdout())
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// `inner` に書き出すライタを生成する。
+    pub fn new(inner: W) -> Writer<W> {
+        Writer {
+            inner: BufWriter::new(inner),
+        }
+    }
+
+    /// 値を書き込む。
+    pub fn print<T: Display>(&mut self, value: T) {
+        write!(self.inner, "{}", value).expect("failed to write to output");
+    }
+
+    /// 値を書き込み、改行する。
+    pub fn println<T: Display>(&mut self, value: T) {
+        writeln!(self.inner, "{}", value).expect("failed to write to output");
+    }
+
+    /// 各要素を `sep` で区切って書き込み、改行する。自由関数の `print_iter` と同じ出力になる。
+    pub fn print_iter<I>(&mut self, iter: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for (i, value) in iter.into_iter().enumerate() {
+            if i > 0 {
+                self.print(sep);
+            }
+            self.print(value);
+        }
+        self.println("");
+    }
+
+    /// バッファに溜まった内容を書き出す。破棄されるときにも自動で書き出される。
+    pub fn flush(&mut self) {
+        self.inner.flush().expect("failed to flush output");
+    }
+}
+
+/// 各要素を `sep` で区切って連結した文字列を返す。
+///
+/// ```
+/// # use procon_lib::pcl::polyfill::io::join;
+/// // use crate::pcl::polyfill::io::join;
+/// assert_eq!(join(&[1, 2, 3], " "), "1 2 3");
+/// assert_eq!(join(vec!['a', 'b'], ""), "ab");
+/// ```
+pub fn join<T: Display, I: IntoIterator<Item = T>>(iter: I, sep: &str) -> String {
+    let mut res = String::new();
+    for (i, value) in iter.into_iter().enumerate() {
+        if i > 0 {
+            res.push_str(sep);
+        }
+        res.push_str(&value.to_string());
+    }
+
+    res
+}
+
+/// 各要素を `sep` で区切って標準出力に書き出し、改行する。`Writer::print_iter` と同じ出力になる。
+pub fn print_iter<T: Display, I: IntoIterator<Item = T>>(iter: I, sep: &str) {
+    println!("{}", join(iter, sep));
+}
+
+/// 標準入力から空白で区切られたトークンを一つ読み込む。
+pub fn read_token() -> String {
+    let stdin = io::stdin();
+    let lock = stdin.lock();
+    read_token_from(lock)
+}
+
+/// 標準入力から空白で区切られたトークンを一つ読み込み、`T` として解釈する。
+pub fn read<T: FromStr>() -> T {
+    let stdin = io::stdin();
+    let lock = stdin.lock();
+    read_from(lock)
+}
+
+/// 標準入力から空白で区切られたトークンを一つ読み込み、文字の配列として返す。
+pub fn read_chars() -> Vec<char> {
+    let stdin = io::stdin();
+    let lock = stdin.lock();
+    read_chars_from(lock)
+}
+
+/// 標準入力から空白で区切られたトークンを一つ読み込み、バイト列として返す。
+pub fn read_bytes() -> Vec<u8> {
+    let stdin = io::stdin();
+    let lock = stdin.lock();
+    read_bytes_from(lock)
+}
+
+/// 標準入力から一行読み込み、末尾の改行を取り除いて返す。
+pub fn read_line() -> String {
+    let stdin = io::stdin();
+    let lock = stdin.lock();
+    read_line_from(lock)
+}
+
+/// 標準出力のバッファを書き出す。インタラクティブな問題で、応答を読む前に呼ぶ。
+pub fn flush() {
+    io::stdout().flush().expect("failed to flush stdout");
+}
+
+/// 標準入力から空白で区切られたトークンを `n` 個読み込み、それぞれ `T` として解釈する。
+pub fn read_vec<T: FromStr>(n: usize) -> Vec<T> {
+    let stdin = io::stdin();
+    let lock = stdin.lock();
+    read_vec_from(lock, n)
+}
+
+/// 標準入力から空白で区切られたトークンを `h * w` 個読み込み、`h` 行 `w` 列の行列として返す。
+pub fn read_matrix<T: FromStr>(h: usize, w: usize) -> Vec<Vec<T>> {
+    let stdin = io::stdin();
+    let lock = stdin.lock();
+    read_matrix_from(lock, h, w)
+}
+
+/// ちょうど `N` 個のトークンを読み込み、固定長の配列として返す。
+#[cfg(feature = "rust-151")]
+pub fn read_array_from<R: Read, T: FromStr, const N: usize>(mut read: R) -> [T; N] {
+    use std::convert::TryInto;
+
+    let values: Vec<T> = (0..N).map(|_| read_from(&mut read)).collect();
+    match values.try_into() {
+        Ok(array) => array,
+        Err(_) => unreachable!("exactly N values are read"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn read_tokens() {
+        let mut input = Cursor::new("  12 hello\n\n -3\tworld ");
+        assert_eq!(read_from::<_, u32>(&mut input), 12);
+        assert_eq!(read_token_from(&mut input), "hello");
+        assert_eq!(read_from::<_, i64>(&mut input), -3);
+        assert_eq!(read_token_from(&mut input), "world");
+        assert_eq!(read_token_from(&mut input), "");
+    }
+
+    #[test]
+    fn read_chars_and_bytes() {
+        let mut input = Cursor::new("abc\n  xyz 12");
+        assert_eq!(read_chars_from(&mut input), vec!['a', 'b', 'c']);
+        assert_eq!(read_bytes_from(&mut input), b"xyz".to_vec());
+        assert_eq!(read_from::<_, u32>(&mut input), 12);
+        assert!(read_chars_from(&mut input).is_empty());
+    }
+
+    #[test]
+    fn read_vec_and_matrix() {
+        let mut input = Cursor::new("3 -1 4\n1 2 3\n4 5 6\n7 8\nx");
+        let v: Vec<i64> = read_vec_from(&mut input, 3);
+        assert_eq!(v, vec![3, -1, 4]);
+        let m: Vec<Vec<u8>> = read_matrix_from(&mut input, 2, 4);
+        assert_eq!(m, vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]);
+        assert_eq!(read_token_from(&mut input), "x");
+
+        assert!(read_vec_from::<_, i32>(Cursor::new(""), 0).is_empty());
+        let m: Vec<Vec<i32>> = read_matrix_from(Cursor::new(""), 3, 0);
+        assert_eq!(m, vec![vec![]; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_vec_parse_failure() {
+        read_vec_from::<_, u32>(Cursor::new("1 2 -3"), 3);
+    }
+
+    #[test]
+    fn scanner_many_tokens() {
+        let n = 200_000;
+        let mut input = n.to_string();
+        for i in 0..n {
+            input.push(if i % 10 == 9 { '\n' } else { ' ' });
+            input.push_str(&(i as i64 * 7 - 1000).to_string());
+        }
+        input.push_str("\r\n  tail\t");
+
+        let mut sc = Scanner::new(Cursor::new(input));
+        let len: usize = sc.next();
+        let values: Vec<i64> = sc.next_vec(len);
+        assert_eq!(values.len(), n);
+        assert!(values
+            .iter()
+            .enumerate()
+            .all(|(i, &v)| v == i as i64 * 7 - 1000));
+        assert_eq!(sc.next_token(), "tail");
+        assert_eq!(sc.next_token(), "");
+        assert_eq!(sc.next_token(), "");
+    }
+
+    #[test]
+    fn scanner_mixed_tokens() {
+        let mut sc = Scanner::new(Cursor::new("  abc 1.5\n\n-3 x"));
+        assert_eq!(sc.next_token(), "abc");
+        assert_eq!(sc.next::<f64>(), 1.5);
+        assert_eq!(sc.next::<i8>(), -3);
+        assert_eq!(sc.next::<char>(), 'x');
+        assert!(sc.next_vec::<u32>(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn scanner_parse_failure() {
+        Scanner::new(Cursor::new("12a")).next::<i32>();
+    }
+
+    #[test]
+    fn writer_to_vec() {
+        let mut out = Vec::new();
+        {
+            let mut writer = Writer::new(&mut out);
+            writer.print("a");
+            writer.print(1.5);
+            writer.println('c');
+            writer.print_iter(vec![3, -1, 4], " ");
+            writer.print_iter(&["x", "y"], ", ");
+            writer.print_iter(Vec::<i32>::new(), " ");
+            for i in 0..10_000 {
+                writer.println(i);
+            }
+        }
+
+        let mut expected = "a1.5c\n3 -1 4\nx, y\n\n".to_string();
+        for i in 0..10_000 {
+            expected.push_str(&format!("{}\n", i));
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn writer_flush() {
+        let mut out = Vec::new();
+        let mut writer = Writer::new(&mut out);
+        writer.print(42);
+        writer.flush();
+        assert_eq!(writer.inner.get_ref().as_slice(), b"42");
+    }
+
+    #[test]
+    fn read_lines() {
+        let mut input = Cursor::new("hello world\r\n\n  x y \nlast");
+        assert_eq!(read_line_from(&mut input), "hello world");
+        assert_eq!(read_line_from(&mut input), "");
+        assert_eq!(read_line_from(&mut input), "  x y ");
+        assert_eq!(read_line_from(&mut input), "last");
+        assert_eq!(read_line_from(&mut input), "");
+
+        // トークンを読んだ後に、行の残りを読める。
+        let mut input = Cursor::new("3 rest of line\nnext");
+        assert_eq!(read_from::<_, i32>(&mut input), 3);
+        assert_eq!(read_line_from(&mut input), "rest of line");
+        assert_eq!(read_token_from(&mut input), "next");
+    }
+
+    #[test]
+    fn interactive_exchange() {
+        // ジャッジは秘密の値 secret を持ち、質問 "? x" に x <= secret かどうかを答える。応答は質問
+        // を受け取ってから初めて入力に現れるので、先読みすると読み込みが終わってしまう。
+        let secret = 37;
+        let mut queries = Vec::new();
+        let mut responses = Cursor::new(b"100\n".to_vec());
+
+        let n: u32 = read_from(&mut responses);
+        let (mut lo, mut hi) = (1, n + 1);
+        let mut count = 0;
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            let start = queries.len();
+            {
+                let mut writer = Writer::new(&mut queries);
+                writer.println(format!("? {}", mid));
+                writer.flush();
+            }
+
+            // ジャッジが質問を読み、応答を書き足す。
+            let mut query = Cursor::new(&queries[start..]);
+            assert_eq!(read_token_from(&mut query), "?");
+            let x: u32 = read_from(&mut query);
+            let answer = if x <= secret { "Yes\n" } else { "No\n" };
+            responses.get_mut().extend_from_slice(answer.as_bytes());
+
+            if read_line_from(&mut responses) == "Yes" {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+            count += 1;
+            // 応答をちょうど読み切っている。
+            assert_eq!(responses.position() as usize, responses.get_ref().len());
+        }
+
+        assert_eq!(lo, secret);
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn join_with_separators() {
+        let v = vec![3i64, -1, 4];
+        assert_eq!(join(&v, " "), "3 -1 4");
+        assert_eq!(join(&v, "\n"), "3\n-1\n4");
+        assert_eq!(join(&v, ", "), "3, -1, 4");
+        assert_eq!(join(&v, ""), "3-14");
+        assert_eq!(join(v.iter().map(|x| x * 2), " "), "6 -2 8");
+        assert_eq!(join(Some("only"), " "), "only");
+        assert_eq!(join(Vec::<i32>::new(), " "), "");
+        assert_eq!(join(Vec::<String>::new(), ""), "");
+    }
+
+    #[test]
+    fn read_delimited() {
+        let values: Vec<i32> = read_delimited_from(Cursor::new("1,2,3"), ',');
+        assert_eq!(values, vec![1, 2, 3]);
+
+        // 空白と区切り文字が混ざっていてもよく、空のフィールドは読み飛ばす。
+        let values: Vec<i64> = read_delimited_from(Cursor::new("4, -5,,6\n7 8\n"), ',');
+        assert_eq!(values, vec![4, -5, 6, 7, 8]);
+        let values: Vec<String> = read_delimited_from(Cursor::new("a;b c\n;d"), ';');
+        assert_eq!(values, vec!["a", "b", "c", "d"]);
+        assert!(read_delimited_from::<_, u8>(Cursor::new(" , \n"), ',').is_empty());
+    }
+
+    #[test]
+    fn read_weighted_edges() {
+        use crate::pcl::traits::math::graph::ReadonlyGraph;
+
+        let mut input = Cursor::new("1 2 10\n3 1 -4\n2 2 7\n42");
+        let graph: EdgeList<i64> = read_weighted_edges_from(&mut input, 3, true);
+        assert_eq!(graph.size(), 3);
+        assert_eq!(
+            graph.edges(),
+            &[Edge::new(0, 1, 10), Edge::new(2, 0, -4), Edge::new(1, 1, 7)]
+        );
+        // 続きをそのまま読み込める。
+        assert_eq!(read_from::<_, i32>(&mut input), 42);
+
+        let graph: EdgeList<f64> = read_weighted_edges_from(Cursor::new("0 4 1.5"), 1, false);
+        assert_eq!(graph.size(), 5);
+        assert_eq!(graph.edges(), &[Edge::new(0, 4, 1.5)]);
+
+        let graph: EdgeList<i64> = read_weighted_edges_from(Cursor::new(""), 0, true);
+        assert_eq!(graph.size(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_parse_failure() {
+        read_from::<_, i32>(Cursor::new("abc"));
+    }
+
+    #[cfg(feature = "rust-151")]
+    #[test]
+    fn read_array() {
+        let mut input = Cursor::new("1 -2 3 4");
+        let array: [i32; 3] = read_array_from(&mut input);
+        assert_eq!(array, [1, -2, 3]);
+        assert_eq!(read_from::<_, i32>(&mut input), 4);
+    }
+}
@@ -1,2 +1,3 @@
+pub mod io;
 #[cfg(not(feature = "crates-atc-2020"))]
 pub mod num;
@@ -34,11 +34,13 @@ impl_for_primitive!(u8; 0, 1);
 impl_for_primitive!(u16; 0, 1);
 impl_for_primitive!(u32; 0, 1);
 impl_for_primitive!(u64; 0, 1);
+impl_for_primitive!(u128; 0, 1);
 impl_for_primitive!(usize; 0, 1);
 impl_for_primitive!(i8; 0, 1);
 impl_for_primitive!(i16; 0, 1);
 impl_for_primitive!(i32; 0, 1);
 impl_for_primitive!(i64; 0, 1);
+impl_for_primitive!(i128; 0, 1);
 impl_for_primitive!(isize; 0, 1);
 impl_for_primitive!(f32; 0.0, 1.0);
 impl_for_primitive!(f64; 0.0, 1.0);
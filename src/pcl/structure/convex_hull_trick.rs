@@ -0,0 +1,172 @@
+//! 直線の集合の最小値を求める Convex Hull Trick `ConvexHullTrick` を定義する。
+//!
+//! `dp[i] = min_j (a_j * x_i + b_j)` の形の DP の高速化に使う。直線は傾きの大きい順に追加しなけれ
+//! ばならない。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::convex_hull_trick::ConvexHullTrick;
+//! // use crate::pcl::structure::convex_hull_trick::ConvexHullTrick;
+//! let mut cht = ConvexHullTrick::new();
+//! cht.add_line(2, 0);
+//! cht.add_line(0, 3);
+//! cht.add_line(-1, 6);
+//! assert_eq!(cht.query(0), 0);
+//! assert_eq!(cht.query(2), 3);
+//! assert_eq!(cht.query(5), 1);
+//! ```
+
+/// 直線 `y = a x + b` を追加し、ある `x` での最小値を求めるデータ構造。
+///
+/// 最小値を与えうる直線だけを傾きの降順に保持する (下側凸包) 。
+#[derive(Debug, Clone, Default)]
+pub struct ConvexHullTrick {
+    /// (傾き, 切片)
+    lines: Vec<(i64, i64)>,
+}
+
+impl ConvexHullTrick {
+    /// 直線を持たない状態で生成する。
+    pub fn new() -> ConvexHullTrick {
+        ConvexHullTrick { lines: Vec::new() }
+    }
+
+    /// 保持している直線の本数を返す。最小値を与えない直線は取り除かれるので、追加した本数より少な
+    /// いことがある。
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// 直線を一本も持たないかどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// 直線 `y = slope * x + intercept` を追加する。`slope` はそれまでに追加したどの直線の傾きよ
+    /// りも大きくてはならない。
+    ///
+    /// # 計算量
+    ///
+    /// ならし O(1)
+    pub fn add_line(&mut self, slope: i64, intercept: i64) {
+        if let Some(&(a, b)) = self.lines.last() {
+            assert!(
+                slope <= a,
+                "slopes must be added in non-increasing order: {} after {}",
+                slope,
+                a
+            );
+            if slope == a {
+                if b <= intercept {
+                    return;
+                }
+                self.lines.pop();
+            }
+        }
+
+        let line = (slope, intercept);
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if !is_needless(self.lines[n - 2], self.lines[n - 1], line) {
+                break;
+            }
+            self.lines.pop();
+        }
+        self.lines.push(line);
+    }
+
+    /// `x` における直線の値の最小値を返す。直線が一本もなければ panic する。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn query(&self, x: i64) -> i64 {
+        assert!(!self.lines.is_empty(), "no lines have been added");
+
+        let eval = |i: usize| {
+            let (a, b) = self.lines[i];
+            a * x + b
+        };
+
+        // 凸包上では x での値が最小になるところまで単調に減少する。
+        let (mut lo, mut hi) = (0, self.lines.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if eval(mid) <= eval(mid + 1) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        eval(lo)
+    }
+}
+
+/// 傾きが `l1 > l2 > l3` の順の三直線について、`l2` が最小値を与える区間がなければ true を返す。
+fn is_needless(l1: (i64, i64), l2: (i64, i64), l3: (i64, i64)) -> bool {
+    // l1 と l2 の交点が l2 と l3 の交点より右にないかを、オーバーフローしないよう i128 で比べる。
+    let (a1, b1) = (i128::from(l1.0), i128::from(l1.1));
+    let (a2, b2) = (i128::from(l2.0), i128::from(l2.1));
+    let (a3, b3) = (i128::from(l3.0), i128::from(l3.1));
+    (b3 - b2) * (a1 - a2) <= (b2 - b1) * (a2 - a3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn convex_hull_trick_small() {
+        let lines = [(3, -4), (1, 0), (1, 2), (0, 1), (-2, 10), (-2, 5), (-5, 20)];
+        let mut cht = ConvexHullTrick::new();
+        assert!(cht.is_empty());
+        for &(a, b) in &lines {
+            cht.add_line(a, b);
+        }
+        assert!(cht.len() < lines.len());
+
+        for x in -10..=10 {
+            let expected = lines.iter().map(|&(a, b)| a * x + b).min().unwrap();
+            assert_eq!(cht.query(x), expected, "x = {}", x);
+        }
+    }
+
+    #[test]
+    fn convex_hull_trick_random() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for _ in 0..100 {
+            let n = (rng.next_u64() % 30 + 1) as usize;
+            let mut lines: Vec<(i64, i64)> = (0..n)
+                .map(|_| {
+                    (
+                        (rng.next_u64() % 41) as i64 - 20,
+                        (rng.next_u64() % 2001) as i64 - 1000,
+                    )
+                })
+                .collect();
+            lines.sort_by_key(|&(a, _)| Reverse(a));
+
+            let mut cht = ConvexHullTrick::new();
+            for &(a, b) in &lines {
+                cht.add_line(a, b);
+            }
+            for x in -100..=100 {
+                let expected = lines.iter().map(|&(a, b)| a * x + b).min().unwrap();
+                assert_eq!(cht.query(x), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn convex_hull_trick_increasing_slope() {
+        let mut cht = ConvexHullTrick::new();
+        cht.add_line(1, 0);
+        cht.add_line(2, 0);
+    }
+}
@@ -1,4 +1,5 @@
-//! 素集合データ構造 `DisjointSets` を定義する。
+//! 素集合データ構造 `DisjointSets` と、重み付きの `WeightedDisjointSets` 、任意の要素を扱える
+//! `DisjointSetsMap` を定義する。
 //!
 //! いわゆる Union-Find 木で、互いに素であるような集合の族を扱う道具である。二つの粗集合のマージと、
 //! 二つの要素が同じ集合に属しているかどうかの判定を高速に行える。
@@ -26,6 +27,8 @@
 //!
 //! assert!(!uf.merge(1, 3));
 //! ```
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::mem::swap;
 
 /// 素集合データ構造。
@@ -43,6 +46,13 @@ impl DisjointSets {
         }
     }
 
+    /// 独立した要素を一つ追加し、そのインデックスを返す。
+    fn push(&mut self) -> usize {
+        self.par.push(-1);
+        self.size += 1;
+        self.par.len() - 1
+    }
+
     /// 二つのグループをマージする。元々同じグループに属していたなら false を返す。
     ///
     /// # 計算量
@@ -75,6 +85,34 @@ impl DisjointSets {
         true
     }
 
+    /// 二つのグループを、根のうちインデックスの小さい方が新しい根になるようにマージする。元々同じグ
+    /// ループに属していたなら false を返す。
+    ///
+    /// すべてのマージをこの関数で行えば、各集合の根 (`root()` の戻り値) は常にその集合の最小の要素
+    /// になる。ただし木の高さを抑える工夫をしなくなるため、`merge` よりも遅くなることがある。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(log n)
+    pub fn merge_keep_min(&mut self, x: usize, y: usize) -> bool {
+        let len = self.par.len();
+        assert!(x < len, "index out of range: x is {} but len is {}", x, len);
+        assert!(y < len, "index out of range: y is {} but len is {}", y, len);
+
+        let x = self.root(x);
+        let y = self.root(y);
+        if x == y {
+            return false;
+        }
+
+        let (root, child) = if x < y { (x, y) } else { (y, x) };
+        self.par[root] += self.par[child];
+        self.par[child] = root as i64;
+        self.size -= 1;
+
+        true
+    }
+
     /// ある二つの要素が同じ集合に属しているかどうかを確認する。
     ///
     /// # 計算量
@@ -90,14 +128,20 @@ impl DisjointSets {
     ///
     /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
     pub fn root(&mut self, x: usize) -> usize {
-        let parx = self.par[x];
-        if parx < 0 {
-            x
-        } else {
-            let root = self.root(parx as usize);
-            self.par[x] = root as i64;
-            root
+        // 深い木でスタックがあふれないよう、再帰を使わずに根を探してから経路を圧縮する。
+        let mut root = x;
+        while self.par[root] >= 0 {
+            root = self.par[root] as usize;
+        }
+
+        let mut cur = x;
+        while cur != root {
+            let next = self.par[cur] as usize;
+            self.par[cur] = root as i64;
+            cur = next;
         }
+
+        root
     }
 
     /// ある要素が属している集合の要素数を求める。
@@ -123,6 +167,288 @@ impl DisjointSets {
     pub fn size(&self) -> usize {
         self.size
     }
+
+    /// 各集合の代表元 (根) を、インデックスの小さい順に並べて返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn roots(&mut self) -> Vec<usize> {
+        (0..self.par.len()).filter(|&x| self.par[x] < 0).collect()
+    }
+
+    /// 素集合の族を、各集合に属する要素のリストのリストとして返す。
+    ///
+    /// 各集合の要素はインデックスの小さい順に並び、集合は最小の要素の小さい順に並ぶ。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(n A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.par.len();
+        // 根からグループの番号への対応
+        let mut group_of_root = vec![None; n];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.size);
+        for x in 0..n {
+            let root = self.root(x);
+            let idx = match group_of_root[root] {
+                Some(idx) => idx,
+                None => {
+                    groups.push(Vec::with_capacity(self.size_of(root)));
+                    group_of_root[root] = Some(groups.len() - 1);
+                    groups.len() - 1
+                }
+            };
+            groups[idx].push(x);
+        }
+
+        groups
+    }
+
+    /// 全部の素集合の個数 (連結成分の個数) を求める。`size()` と同じ。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn num_components(&self) -> usize {
+        self.size
+    }
+
+    /// 全体を一つの集合にするために、追加で必要なマージの回数 (辺の本数) の最小値を求める。
+    ///
+    /// すなわち `num_components() - 1` である。要素が一つもない場合は 0 を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn edges_to_connect(&self) -> usize {
+        self.num_components().saturating_sub(1)
+    }
+}
+
+/// 任意の要素を扱える素集合データ構造。
+///
+/// 初めて見た要素に順にインデックスを割り当て、インデックスベースの `DisjointSets` に処理を任せる。
+/// 文字列や座標などを直接要素として扱いたいときに使う。
+///
+/// ```
+/// # use procon_lib::pcl::structure::disjoint_sets::DisjointSetsMap;
+/// // use crate::pcl::structure::disjoint_sets::DisjointSetsMap;
+/// let mut uf = DisjointSetsMap::new();
+/// assert!(uf.merge(&"alice", &"bob"));
+/// assert!(uf.merge(&"bob", &"carol"));
+/// assert!(uf.in_same(&"alice", &"carol"));
+/// assert!(!uf.in_same(&"alice", &"dave"));
+/// assert_eq!(uf.size_of(&"bob"), 3);
+/// ```
+pub struct DisjointSetsMap<T> {
+    index: HashMap<T, usize>,
+    inner: DisjointSets,
+}
+
+impl<T: Eq + Hash + Clone> Default for DisjointSetsMap<T> {
+    fn default() -> DisjointSetsMap<T> {
+        DisjointSetsMap::new()
+    }
+}
+
+impl<T: Eq + Hash + Clone> DisjointSetsMap<T> {
+    /// 要素を一つも含まない素集合の族を生成する。
+    pub fn new() -> DisjointSetsMap<T> {
+        DisjointSetsMap {
+            index: HashMap::new(),
+            inner: DisjointSets::new(0),
+        }
+    }
+
+    /// 要素のインデックスを取得する。初めて見る要素なら、新しく独立した集合として登録する。
+    fn index_of(&mut self, x: &T) -> usize {
+        if let Some(&idx) = self.index.get(x) {
+            return idx;
+        }
+
+        let idx = self.inner.push();
+        self.index.insert(x.clone(), idx);
+        idx
+    }
+
+    /// 二つのグループをマージする。元々同じグループに属していたなら false を返す。
+    ///
+    /// まだ登録されていない要素は自動的に登録される。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn merge(&mut self, x: &T, y: &T) -> bool {
+        let x = self.index_of(x);
+        let y = self.index_of(y);
+        self.inner.merge(x, y)
+    }
+
+    /// 二つの要素が同じグループに属しているかを判定する。
+    ///
+    /// まだ登録されていない要素は自動的に登録される。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn in_same(&mut self, x: &T, y: &T) -> bool {
+        let x = self.index_of(x);
+        let y = self.index_of(y);
+        self.inner.in_same(x, y)
+    }
+
+    /// 要素が属するグループの要素数を取得する。登録されていない要素なら 1 を返す。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn size_of(&mut self, x: &T) -> usize {
+        match self.index.get(x) {
+            Some(&idx) => self.inner.size_of(idx),
+            None => 1,
+        }
+    }
+
+    /// 登録されている要素からなるグループの個数を取得する。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+/// 重み付き素集合データ構造。
+///
+/// 各要素にポテンシャル (ある基準からの差分) を持たせ、同じ集合に属する二つの要素のポテンシャルの差
+/// を求められる。「y は x より w だけ大きい」という形の条件の集まりが矛盾していないかを判定するのに
+/// 使う。
+///
+/// ```
+/// # use procon_lib::pcl::structure::disjoint_sets::WeightedDisjointSets;
+/// // use crate::pcl::structure::disjoint_sets::WeightedDisjointSets;
+/// let mut uf = WeightedDisjointSets::new(4);
+/// assert!(uf.merge(0, 1, 3));
+/// assert!(uf.merge(1, 2, -5));
+/// assert_eq!(uf.diff(0, 2), Some(-2));
+/// assert_eq!(uf.diff(0, 3), None);
+/// assert!(uf.merge(2, 0, 2));
+/// assert!(!uf.merge(2, 0, 1));
+/// ```
+pub struct WeightedDisjointSets {
+    par: Vec<i64>,
+    /// 親から見たポテンシャルの差分。
+    pot: Vec<i64>,
+    size: usize,
+}
+
+impl WeightedDisjointSets {
+    /// それぞれの要素が独立している n 個の素集合の族を生成する。
+    pub fn new(n: usize) -> WeightedDisjointSets {
+        WeightedDisjointSets {
+            par: vec![-1; n],
+            pot: vec![0; n],
+            size: n,
+        }
+    }
+
+    /// `pot(y) - pot(x) = w` という条件を加えて、二つのグループをマージする。
+    ///
+    /// 既に同じグループに属している場合は何もせず、条件がそれまでの条件と矛盾していなければ true
+    /// を、矛盾していれば false を返す。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn merge(&mut self, x: usize, y: usize, w: i64) -> bool {
+        let len = self.par.len();
+        assert!(x < len, "index out of range: x is {} but len is {}", x, len);
+        assert!(y < len, "index out of range: y is {} but len is {}", y, len);
+
+        let mut rx = self.root(x);
+        let mut ry = self.root(y);
+        // 根 rx から見た根 ry のポテンシャル
+        let mut w = w + self.pot[x] - self.pot[y];
+        if rx == ry {
+            return w == 0;
+        }
+
+        if self.par[rx] > self.par[ry] {
+            swap(&mut rx, &mut ry);
+            w = -w;
+        }
+
+        self.par[rx] += self.par[ry];
+        self.par[ry] = rx as i64;
+        self.pot[ry] = w;
+        self.size -= 1;
+
+        true
+    }
+
+    /// 二つの要素が同じ集合に属していれば `pot(y) - pot(x)` を返す。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn diff(&mut self, x: usize, y: usize) -> Option<i64> {
+        if self.in_same(x, y) {
+            Some(self.pot[y] - self.pot[x])
+        } else {
+            None
+        }
+    }
+
+    /// ある二つの要素が同じ集合に属しているかどうかを確認する。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn in_same(&mut self, x: usize, y: usize) -> bool {
+        self.root(x) == self.root(y)
+    }
+
+    /// ある要素が属している集合を求める。
+    ///
+    /// 経路圧縮の際にポテンシャルも更新するので、この関数を呼んだ後は `x` のポテンシャルは根から見
+    /// た値になる。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn root(&mut self, x: usize) -> usize {
+        let parx = self.par[x];
+        if parx < 0 {
+            x
+        } else {
+            let parx = parx as usize;
+            let root = self.root(parx);
+            self.pot[x] += self.pot[parx];
+            self.par[x] = root as i64;
+            root
+        }
+    }
+
+    /// ある要素が属している集合の要素数を求める。
+    ///
+    /// # 計算量
+    ///
+    /// ならし計算量で O(A(n)) 。ただし A(n) はアッカーマン関数の逆関数。
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+        -self.par[root] as usize
+    }
+
+    /// 全部の素集合の個数を求める。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn size(&self) -> usize {
+        self.size
+    }
 }
 
 #[cfg(test)]
@@ -152,4 +478,172 @@ mod tests {
 
         assert!(!uf.merge(1, 3));
     }
+
+    #[test]
+    fn disjoint_sets_edges_to_connect() {
+        let mut uf = DisjointSets::new(6);
+        assert_eq!(uf.num_components(), 6);
+        assert_eq!(uf.edges_to_connect(), 5);
+
+        uf.merge(0, 1);
+        uf.merge(2, 3);
+        uf.merge(1, 0);
+        assert_eq!(uf.num_components(), 4);
+        assert_eq!(uf.edges_to_connect(), 3);
+
+        uf.merge(0, 3);
+        uf.merge(4, 5);
+        uf.merge(5, 1);
+        assert_eq!(uf.num_components(), 1);
+        assert_eq!(uf.edges_to_connect(), 0);
+
+        assert_eq!(DisjointSets::new(0).edges_to_connect(), 0);
+    }
+
+    #[test]
+    fn disjoint_sets_root_long_chain() {
+        // 経路圧縮の前に長い鎖ができていても、スタックがあふれない。
+        let n = 1_000_000;
+        let mut uf = DisjointSets::new(n);
+        for i in 0..n - 1 {
+            uf.par[i] = (i + 1) as i64;
+        }
+        uf.par[n - 1] = -(n as i64);
+        uf.size = 1;
+
+        assert_eq!(uf.root(0), n - 1);
+        assert!((0..n - 1).all(|i| uf.par[i] == (n - 1) as i64));
+        assert_eq!(uf.size_of(n / 2), n);
+        assert!(uf.in_same(0, n / 3));
+    }
+
+    #[test]
+    fn disjoint_sets_groups() {
+        let mut uf = DisjointSets::new(7);
+        assert_eq!(
+            uf.groups(),
+            vec![
+                vec![0],
+                vec![1],
+                vec![2],
+                vec![3],
+                vec![4],
+                vec![5],
+                vec![6]
+            ]
+        );
+
+        uf.merge(5, 1);
+        uf.merge(3, 6);
+        uf.merge(0, 4);
+        uf.merge(6, 1);
+        assert_eq!(uf.groups(), vec![vec![0, 4], vec![1, 3, 5, 6], vec![2]]);
+
+        let roots = uf.roots();
+        assert_eq!(roots.len(), uf.size());
+        for group in uf.groups() {
+            let root = uf.root(group[0]);
+            assert_eq!(roots.iter().filter(|&&r| r == root).count(), 1);
+        }
+
+        uf.merge(2, 4);
+        uf.merge(2, 6);
+        assert_eq!(uf.groups(), vec![vec![0, 1, 2, 3, 4, 5, 6]]);
+        assert_eq!(uf.roots().len(), 1);
+        assert!(DisjointSets::new(0).groups().is_empty());
+    }
+
+    #[test]
+    fn disjoint_sets_merge_keep_min() {
+        let mut uf = DisjointSets::new(8);
+        assert!(uf.merge_keep_min(5, 7));
+        assert_eq!(uf.root(7), 5);
+        assert!(uf.merge_keep_min(6, 5));
+        assert_eq!(uf.root(6), 5);
+        assert!(uf.merge_keep_min(3, 4));
+        assert!(uf.merge_keep_min(7, 4));
+        assert_eq!(uf.root(5), 3);
+        assert!(uf.merge_keep_min(2, 0));
+        assert!(!uf.merge_keep_min(4, 6));
+        assert!(uf.merge_keep_min(6, 2));
+
+        for x in [0, 2, 3, 4, 5, 6, 7].iter() {
+            assert_eq!(uf.root(*x), 0);
+        }
+        assert_eq!(uf.root(1), 1);
+        assert_eq!(uf.size_of(4), 7);
+        assert_eq!(uf.size(), 2);
+    }
+
+    #[test]
+    fn weighted_disjoint_sets_consistent() {
+        let mut uf = WeightedDisjointSets::new(6);
+        assert!(uf.merge(0, 1, 5));
+        assert!(uf.merge(2, 3, -2));
+        assert!(uf.merge(4, 3, 7));
+        assert_eq!(uf.diff(2, 4), Some(-9));
+        assert_eq!(uf.diff(0, 2), None);
+
+        assert!(uf.merge(1, 2, 1));
+        assert_eq!(uf.size(), 2);
+        assert_eq!(uf.size_of(4), 5);
+        // pot: 0 -> 0, 1 -> 5, 2 -> 6, 3 -> 4, 4 -> -3
+        let pot = [0, 5, 6, 4, -3];
+        for x in 0..5 {
+            for y in 0..5 {
+                assert_eq!(uf.diff(x, y), Some(pot[y] - pot[x]));
+            }
+        }
+        assert!(uf.merge(4, 0, 3));
+        assert_eq!(uf.diff(5, 0), None);
+    }
+
+    #[test]
+    fn weighted_disjoint_sets_contradiction() {
+        let mut uf = WeightedDisjointSets::new(4);
+        assert!(uf.merge(0, 1, 1));
+        assert!(uf.merge(1, 2, 1));
+        assert!(uf.merge(2, 3, 1));
+        assert!(!uf.merge(0, 3, 2));
+        assert!(!uf.merge(3, 0, 3));
+        assert!(uf.merge(3, 0, -3));
+        // 矛盾した条件は取り込まれない。
+        assert_eq!(uf.diff(0, 3), Some(3));
+    }
+
+    #[test]
+    fn disjoint_sets_map() {
+        let mut uf = DisjointSetsMap::new();
+        let names: Vec<String> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(uf.size(), 0);
+        assert_eq!(uf.size_of(&names[0]), 1);
+        assert_eq!(uf.size(), 0);
+
+        assert!(uf.merge(&names[0], &names[1]));
+        assert!(uf.merge(&names[2], &names[3]));
+        assert!(!uf.merge(&names[1], &names[0]));
+        assert_eq!(uf.size(), 2);
+        assert!(uf.in_same(&names[0], &names[1]));
+        assert!(!uf.in_same(&names[1], &names[2]));
+
+        // in_same で初めて見た要素も登録される。
+        assert!(!uf.in_same(&names[4], &names[0]));
+        assert_eq!(uf.size(), 3);
+
+        assert!(uf.merge(&names[1], &names[3]));
+        assert!(uf.in_same(&names[0], &names[2]));
+        assert_eq!(uf.size_of(&names[3]), 4);
+        assert_eq!(uf.size_of(&names[4]), 1);
+        assert_eq!(uf.size(), 2);
+
+        let mut grid = DisjointSetsMap::new();
+        assert!(grid.merge(&(0, 0), &(0, 1)));
+        assert!(grid.merge(&(1, 1), &(0, 1)));
+        assert!(grid.in_same(&(0, 0), &(1, 1)));
+        assert!(!grid.in_same(&(0, 0), &(1, 0)));
+    }
 }
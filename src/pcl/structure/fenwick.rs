@@ -0,0 +1,564 @@
+//! フェニック木 (Binary Indexed Tree) `FenwickTree` とその派生を定義する。
+//!
+//! フェニック木は、配列に対して次の操作が高速に行えるデータ構造である。
+//!
+//! - ある 1 要素への値の加算 (`add`) ― O(log n)
+//! - ある区間の和 (`sum`) ― O(log n)
+//!
+//! セグメント木と比べてできることは少ないが、定数倍が軽くメモリも半分で済む。任意の区間の和を先頭か
+//! らの和の差として求めるため、値は (可換な) 群 (`Group`) でなければならない。
+//!
+//! 区間加算と区間和を扱う `RangeFenwickTree` や、二次元の `FenwickTree2D` も用意している。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::fenwick::FenwickTree;
+//! # use procon_lib::pcl::traits::math::group::Additive as A;
+//! // use crate::pcl::structure::fenwick::FenwickTree;
+//! // use crate::pcl::traits::math::group::Additive as A;
+//! let mut ft = FenwickTree::new(5);
+//! ft.add(0, A(3));
+//! ft.add(2, A(4));
+//! ft.add(4, A(-1));
+//! assert_eq!(ft.sum(..).0, 6);
+//! assert_eq!(ft.sum(1..3).0, 4);
+//! assert_eq!(ft.sum(3..).0, -1);
+//! ```
+
+use crate::pcl::traits::math::group::Additive;
+use crate::pcl::traits::math::Group;
+use crate::pcl::utils::range;
+use std::fmt;
+use std::ops::RangeBounds;
+
+/// フェニック木。
+pub struct FenwickTree<T> {
+    /// 1-indexed で、 `data[i]` は区間 `(i - (i & -i), i]` の和を持つ。
+    data: Vec<T>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for FenwickTree<T> {
+    fn fmt<'a>(&self, f: &mut fmt::Formatter<'a>) -> fmt::Result {
+        f.debug_struct("FenwickTree")
+            .field("data", &&self.data[1..])
+            .finish()
+    }
+}
+
+impl<T: Clone> Clone for FenwickTree<T> {
+    fn clone(&self) -> Self {
+        FenwickTree {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Group + Copy,
+{
+    /// すべての要素が単位元であるような長さ `n` のフェニック木を生成する。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn new(n: usize) -> FenwickTree<T> {
+        FenwickTree {
+            data: vec![T::id(); n + 1],
+        }
+    }
+
+    /// 配列の長さを取得する。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.data.len() - 1
+    }
+
+    /// 配列が空かどうかを返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// あるインデックス `idx` の要素に `value` を加える。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn add(&mut self, idx: usize, value: T) {
+        let len = self.len();
+        assert!(
+            idx < len,
+            "index out of range: idx is {} but len is {}",
+            idx,
+            len
+        );
+
+        let mut i = idx + 1;
+        while i <= len {
+            self.data[i] = T::op(self.data[i], value);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 指定された範囲内の総和を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn sum<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let start = range::range_start(&range, 0);
+        let end = range::range_end(&range, self.len());
+        if end <= start {
+            return T::id();
+        }
+
+        T::op(self.prefix_sum(end), T::inv(self.prefix_sum(start)))
+    }
+
+    /// 先頭からの和 `sum(..=idx)` が `target` 以上になる最小の `idx` を返す。そのような `idx` がな
+    /// ければ `len()` を返す。
+    ///
+    /// 群そのものには順序がないので、比較は `less(a, b)` (`a < b` のとき `true`) で与える。先頭から
+    /// の和が単調非減少である (つまり各要素が単位元以上である) ことを仮定する。
+    ///
+    /// 各要素を値の出現回数とすると、`lower_bound(A(k + 1), ..)` は小さい方から `k` 番目
+    /// (0-indexed) の値になる。
+    ///
+    /// ```
+    /// # use procon_lib::pcl::structure::fenwick::FenwickTree;
+    /// # use procon_lib::pcl::traits::math::group::Additive as A;
+    /// // use crate::pcl::structure::fenwick::FenwickTree;
+    /// // use crate::pcl::traits::math::group::Additive as A;
+    /// // 多重集合 {1, 3, 3, 4}
+    /// let mut ft = FenwickTree::new(6);
+    /// ft.add(1, A(1));
+    /// ft.add(3, A(2));
+    /// ft.add(4, A(1));
+    /// let less = |a: &A<i32>, b: &A<i32>| a.0 < b.0;
+    /// assert_eq!(ft.lower_bound(A(1), less), 1);
+    /// assert_eq!(ft.lower_bound(A(3), less), 3);
+    /// assert_eq!(ft.lower_bound(A(4), less), 4);
+    /// assert_eq!(ft.lower_bound(A(5), less), 6);
+    /// ```
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn lower_bound<F>(&self, target: T, mut less: F) -> usize
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let len = self.len();
+        let mut step = 1;
+        while step * 2 <= len {
+            step *= 2;
+        }
+
+        // `pos` 個目までの和が `target` 未満である範囲で、できるだけ先に進む。
+        let mut pos = 0;
+        let mut acc = T::id();
+        while step > 0 {
+            if pos + step <= len {
+                let next = T::op(acc, self.data[pos + step]);
+                if less(&next, &target) {
+                    pos += step;
+                    acc = next;
+                }
+            }
+            step /= 2;
+        }
+
+        pos
+    }
+
+    /// 先頭から `end` 個の要素の和を求める。
+    fn prefix_sum(&self, end: usize) -> T {
+        let mut res = T::id();
+        let mut i = end;
+        while i > 0 {
+            res = T::op(res, self.data[i]);
+            i -= i & i.wrapping_neg();
+        }
+
+        res
+    }
+}
+
+/// 二次元のフェニック木。一点への加算と長方形領域の和を扱う。
+///
+/// ```
+/// # use procon_lib::pcl::structure::fenwick::FenwickTree2D;
+/// # use procon_lib::pcl::traits::math::group::Additive as A;
+/// // use crate::pcl::structure::fenwick::FenwickTree2D;
+/// // use crate::pcl::traits::math::group::Additive as A;
+/// let mut ft = FenwickTree2D::new(3, 4);
+/// ft.add(0, 1, A(2));
+/// ft.add(2, 3, A(5));
+/// ft.add(1, 1, A(-1));
+/// assert_eq!(ft.sum(.., ..).0, 6);
+/// assert_eq!(ft.sum(..2, 1..2).0, 1);
+/// assert_eq!(ft.sum(1.., 2..).0, 5);
+/// ```
+pub struct FenwickTree2D<T> {
+    /// 1-indexed で、各軸について `FenwickTree` と同じ区間の和を持つ。
+    data: Vec<Vec<T>>,
+    height: usize,
+    width: usize,
+}
+
+impl<T: fmt::Debug> fmt::Debug for FenwickTree2D<T> {
+    fn fmt<'a>(&self, f: &mut fmt::Formatter<'a>) -> fmt::Result {
+        f.debug_struct("FenwickTree2D")
+            .field("height", &self.height)
+            .field("width", &self.width)
+            .finish()
+    }
+}
+
+impl<T: Clone> Clone for FenwickTree2D<T> {
+    fn clone(&self) -> Self {
+        FenwickTree2D {
+            data: self.data.clone(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+}
+
+impl<T> FenwickTree2D<T>
+where
+    T: Group + Copy,
+{
+    /// すべての要素が単位元であるような `h` 行 `w` 列のフェニック木を生成する。
+    ///
+    /// # 計算量
+    ///
+    /// O(hw)
+    pub fn new(h: usize, w: usize) -> FenwickTree2D<T> {
+        FenwickTree2D {
+            data: vec![vec![T::id(); w + 1]; h + 1],
+            height: h,
+            width: w,
+        }
+    }
+
+    /// 行列の大きさを取得する。
+    ///
+    /// 戻り値は (高さ, 幅)
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// `y` 行 `x` 列の要素に `value` を加える。
+    ///
+    /// # 計算量
+    ///
+    /// O(log h log w)
+    pub fn add(&mut self, y: usize, x: usize, value: T) {
+        assert!(
+            y < self.height && x < self.width,
+            "index out of range: (y, x) is ({}, {}) but size is ({}, {})",
+            y,
+            x,
+            self.height,
+            self.width
+        );
+
+        let mut i = y + 1;
+        while i <= self.height {
+            let mut j = x + 1;
+            while j <= self.width {
+                self.data[i][j] = T::op(self.data[i][j], value);
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 指定された範囲内の総和を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log h log w)
+    pub fn sum<RY, RX>(&self, yrange: RY, xrange: RX) -> T
+    where
+        RY: RangeBounds<usize>,
+        RX: RangeBounds<usize>,
+    {
+        let ystart = range::range_start(&yrange, 0);
+        let yend = range::range_end(&yrange, self.height);
+        let xstart = range::range_start(&xrange, 0);
+        let xend = range::range_end(&xrange, self.width);
+        if yend <= ystart || xend <= xstart {
+            return T::id();
+        }
+
+        T::op(
+            T::op(
+                T::op(self.prefix_sum(yend, xend), self.prefix_sum(ystart, xstart)),
+                T::inv(self.prefix_sum(ystart, xend)),
+            ),
+            T::inv(self.prefix_sum(yend, xstart)),
+        )
+    }
+
+    /// 先頭から `yend` 行 `xend` 列の長方形領域の和を求める。
+    fn prefix_sum(&self, yend: usize, xend: usize) -> T {
+        let mut res = T::id();
+        let mut i = yend;
+        while i > 0 {
+            let mut j = xend;
+            while j > 0 {
+                res = T::op(res, self.data[i][j]);
+                j -= j & j.wrapping_neg();
+            }
+            i -= i & i.wrapping_neg();
+        }
+
+        res
+    }
+}
+
+/// 区間加算・区間和を扱うフェニック木。
+///
+/// 二本のフェニック木を使い、先頭から `i` 個の和を `b1(i) * i + b2(i)` の形で表す。ここで `b1(i)`,
+/// `b2(i)` はそれぞれのフェニック木の先頭 `i` 個の和である。区間 `[l, r)` に `delta` を加えるとき
+/// は、 `b1` の `l` に `delta` を、 `r` に `-delta` を加え、補正項として `b2` の `l` に
+/// `-delta * l` を、 `r` に `delta * r` を加える。
+///
+/// ```
+/// # use procon_lib::pcl::structure::fenwick::RangeFenwickTree;
+/// // use crate::pcl::structure::fenwick::RangeFenwickTree;
+/// let mut ft = RangeFenwickTree::new(5);
+/// ft.add_range(1..4, 3);
+/// ft.add_range(.., 1);
+/// assert_eq!(ft.sum(..), 14);
+/// assert_eq!(ft.sum(3..), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeFenwickTree {
+    b1: FenwickTree<Additive<i64>>,
+    b2: FenwickTree<Additive<i64>>,
+}
+
+impl RangeFenwickTree {
+    /// すべての要素が 0 であるような長さ `n` の配列を表すフェニック木を生成する。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn new(n: usize) -> RangeFenwickTree {
+        RangeFenwickTree {
+            b1: FenwickTree::new(n),
+            b2: FenwickTree::new(n),
+        }
+    }
+
+    /// 配列の長さを取得する。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.b1.len()
+    }
+
+    /// 配列が空かどうかを返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn is_empty(&self) -> bool {
+        self.b1.is_empty()
+    }
+
+    /// ある区間 `range` の各要素に `delta` を加える。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn add_range<R: RangeBounds<usize>>(&mut self, range: R, delta: i64) {
+        let start = range::range_start(&range, 0);
+        let end = range::range_end(&range, self.len());
+        if end <= start {
+            return;
+        }
+
+        self.b1.add(start, Additive(delta));
+        self.b2.add(start, Additive(-delta * start as i64));
+        if end < self.len() {
+            self.b1.add(end, Additive(-delta));
+            self.b2.add(end, Additive(delta * end as i64));
+        }
+    }
+
+    /// 指定された範囲内の総和を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn sum<R: RangeBounds<usize>>(&self, range: R) -> i64 {
+        let start = range::range_start(&range, 0);
+        let end = range::range_end(&range, self.len());
+        if end <= start {
+            return 0;
+        }
+
+        self.prefix_sum(end) - self.prefix_sum(start)
+    }
+
+    /// 先頭から `end` 個の要素の和を求める。
+    fn prefix_sum(&self, end: usize) -> i64 {
+        self.b1.sum(..end).0 * end as i64 + self.b2.sum(..end).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::math::modint::Modint17;
+    use crate::pcl::test_util::XorShift;
+    use crate::pcl::traits::math::group::Additive as A;
+
+    #[test]
+    fn fenwick_additive() {
+        let n = 23;
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        let mut ft = FenwickTree::new(n);
+        let mut naive = vec![0i64; n];
+        assert_eq!(ft.len(), n);
+
+        for _ in 0..2000 {
+            let a = rng.next_u64() as usize % (n + 1);
+            let b = rng.next_u64() as usize % (n + 1);
+            if rng.next_u64() & 1 == 0 {
+                let idx = a % n;
+                let value = (rng.next_u64() % 100) as i64 - 50;
+                ft.add(idx, A(value));
+                naive[idx] += value;
+            } else {
+                let expected: i64 = if a <= b { naive[a..b].iter().sum() } else { 0 };
+                assert_eq!(ft.sum(a..b).0, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn fenwick_modint() {
+        let mut ft = FenwickTree::new(4);
+        ft.add(1, A(Modint17::new(1_000_000_000)));
+        ft.add(2, A(Modint17::new(10)));
+        ft.add(3, A(Modint17::new(-1)));
+        assert_eq!(ft.sum(..).0, Modint17::new(2));
+        assert_eq!(ft.sum(1..3).0, Modint17::new(3));
+        assert_eq!(ft.sum(..=2).0, Modint17::new(3));
+        assert_eq!(ft.sum(3..).0, Modint17::new(1_000_000_006));
+    }
+
+    #[test]
+    fn fenwick_empty() {
+        let ft = FenwickTree::<A<i32>>::new(0);
+        assert!(ft.is_empty());
+        assert_eq!(ft.sum(..).0, 0);
+    }
+
+    #[test]
+    fn fenwick_lower_bound_order_statistics() {
+        let n = 17;
+        let mut rng = XorShift::new(2_463_534_242);
+        let mut ft = FenwickTree::new(n);
+        let mut values = Vec::new();
+        let less = |a: &A<i64>, b: &A<i64>| a.0 < b.0;
+
+        for _ in 0..500 {
+            let x = rng.next_u64() as usize % n;
+            ft.add(x, A(1));
+            values.push(x);
+            values.sort_unstable();
+
+            // k 番目に小さい値
+            for (k, &v) in values.iter().enumerate() {
+                assert_eq!(ft.lower_bound(A(k as i64 + 1), less), v);
+            }
+            assert_eq!(ft.lower_bound(A(0), less), 0);
+            assert_eq!(ft.lower_bound(A(values.len() as i64 + 1), less), n);
+
+            if rng.next_u64() % 3 == 1 {
+                let idx = rng.next_u64() as usize % values.len();
+                let removed = values.remove(idx);
+                ft.add(removed, A(-1));
+            }
+        }
+    }
+
+    #[test]
+    fn range_fenwick_naive() {
+        let n = 31;
+        let mut rng = XorShift::new(2_463_534_242);
+        let mut ft = RangeFenwickTree::new(n);
+        let mut naive = vec![0i64; n];
+
+        for _ in 0..2000 {
+            let a = rng.next_u64() as usize % (n + 1);
+            let b = rng.next_u64() as usize % (n + 1);
+            let (l, r) = if a <= b { (a, b) } else { (b, a) };
+            match rng.next_u64() % 3 {
+                0 => {
+                    let delta = (rng.next_u64() % 100) as i64 - 50;
+                    ft.add_range(l..r, delta);
+                    for x in &mut naive[l..r] {
+                        *x += delta;
+                    }
+                }
+                1 => {
+                    let idx = a % n;
+                    assert_eq!(ft.sum(idx..=idx), naive[idx]);
+                }
+                _ => {
+                    assert_eq!(ft.sum(l..r), naive[l..r].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fenwick_2d_naive() {
+        let (h, w) = (7, 11);
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        let mut ft = FenwickTree2D::new(h, w);
+        let mut naive = vec![vec![0i64; w]; h];
+        assert_eq!(ft.size(), (h, w));
+
+        for _ in 0..300 {
+            let y = rng.next_u64() as usize % h;
+            let x = rng.next_u64() as usize % w;
+            let value = (rng.next_u64() % 100) as i64 - 50;
+            ft.add(y, x, A(value));
+            naive[y][x] += value;
+
+            let y1 = rng.next_u64() as usize % (h + 1);
+            let y2 = rng.next_u64() as usize % (h + 1);
+            let x1 = rng.next_u64() as usize % (w + 1);
+            let x2 = rng.next_u64() as usize % (w + 1);
+            let mut expected = 0;
+            for row in naive.iter().take(y2).skip(y1) {
+                for &v in row.iter().take(x2).skip(x1) {
+                    expected += v;
+                }
+            }
+            assert_eq!(ft.sum(y1..y2, x1..x2).0, expected);
+        }
+    }
+}
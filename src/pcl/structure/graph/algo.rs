@@ -0,0 +1,1537 @@
+//! グラフを探索する汎用のアルゴリズムを定義する。
+//!
+//! 距離の配列などを決め打ちで返すのではなく、頂点を訪れるたびにコールバックを呼ぶ。頂点ごとに必要
+//! な情報はコールバックの中で自由に記録すればよい。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::graph::{bfs_visit, UndirectedAdjacencyList};
+//! # use procon_lib::pcl::traits::math::graph::Graph;
+//! // use crate::pcl::structure::graph::{bfs_visit, UndirectedAdjacencyList};
+//! // use crate::pcl::traits::math::graph::Graph;
+//! let mut graph = UndirectedAdjacencyList::<i32>::of_size(5);
+//! graph.add_edges(vec![(0, 1), (1, 2), (0, 3), (3, 4)]);
+//!
+//! // 頂点 0 からの辺の本数での距離
+//! let mut dist = vec![None; 5];
+//! bfs_visit(&graph, 0, |v, parent| {
+//!     dist[v] = Some(parent.map_or(0, |p| dist[p].unwrap() + 1));
+//! });
+//! assert_eq!(dist, vec![Some(0), Some(1), Some(2), Some(1), Some(2)]);
+//! ```
+
+use super::{EdgeList, UndirectedAdjacencyList};
+use crate::pcl::compat::num::Zero;
+use crate::pcl::math::matrix::Matrix;
+use crate::pcl::math::modint::consts::ModintConst;
+use crate::pcl::math::modint::Modint;
+use crate::pcl::structure::DisjointSets;
+use crate::pcl::traits::math::graph::{Edge, ProvideAdjacencies, ReadonlyGraph, Undirected};
+use crate::pcl::traits::utils::num::SaturatingAdd;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::ops::Add;
+
+/// `start` から幅優先探索を行い、頂点を訪れた順に `visit(頂点, 親)` を呼ぶ。
+///
+/// 各頂点はたかだか一度しか訪れない。`start` の親は `None` として渡される。`start` から到達できな
+/// い頂点は訪れない。
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn bfs_visit<G, F>(graph: &G, start: usize, mut visit: F)
+where
+    G: ProvideAdjacencies,
+    F: FnMut(usize, Option<usize>),
+{
+    let mut visited = vec![false; graph.size()];
+    let mut queue = VecDeque::new();
+    visited[start] = true;
+    visit(start, None);
+    queue.push_back(start);
+    while let Some(v) = queue.pop_front() {
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            if visited[edge.to] {
+                continue;
+            }
+            visited[edge.to] = true;
+            visit(edge.to, Some(v));
+            queue.push_back(edge.to);
+        }
+    }
+}
+
+/// `start` から各頂点への最短路の辺の本数を求める。到達できない頂点は `None` となる。
+///
+/// 辺のコストは無視する。
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn bfs_dist<G: ProvideAdjacencies>(graph: &G, start: usize) -> Vec<Option<usize>> {
+    let mut dist = vec![None; graph.size()];
+    bfs_visit(graph, start, |v, parent| {
+        dist[v] = Some(parent.map_or(0, |p| dist[p].unwrap() + 1));
+    });
+
+    dist
+}
+
+/// `start` から各頂点への最短路の長さを Dijkstra 法で求める。到達できない頂点は `None` となる。
+///
+/// 辺のコストはすべて非負でなければならない。距離の計算は飽和加算で行うので、非常に大きなコストを
+/// 足し合わせてもオーバーフローしない (値域の最大値で頭打ちになる) 。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{dijkstra, AdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{dijkstra, AdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = AdjacencyList::<i64>::of_size(4);
+/// graph.add_edges(vec![(0, 1, 5), (0, 2, 1), (2, 1, 2)]);
+/// assert_eq!(dijkstra(&graph, 0), vec![Some(0), Some(3), Some(1), None]);
+/// ```
+///
+/// # 計算量
+///
+/// O((V + E) log V)
+pub fn dijkstra<G>(graph: &G, start: usize) -> Vec<Option<G::Cost>>
+where
+    G: ProvideAdjacencies,
+    G::Cost: Ord + SaturatingAdd + Zero + Copy,
+{
+    dijkstra_with_prev(graph, start).0
+}
+
+/// `dijkstra` と同様に最短路の長さを求め、あわせて最短路木での各頂点の直前の頂点を返す。
+///
+/// 戻り値は (距離, 直前の頂点) で、始点の直前の頂点は始点自身、到達できない頂点は `None` とする。
+/// 実際の経路は `reconstruct_path` で復元できる。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{dijkstra_with_prev, reconstruct_path, AdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{dijkstra_with_prev, reconstruct_path, AdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = AdjacencyList::<i64>::of_size(4);
+/// graph.add_edges(vec![(0, 1, 5), (0, 2, 1), (2, 1, 2)]);
+/// let (dist, prev) = dijkstra_with_prev(&graph, 0);
+/// assert_eq!(dist[1], Some(3));
+/// assert_eq!(reconstruct_path(&prev, 1), Some(vec![0, 2, 1]));
+/// assert_eq!(reconstruct_path(&prev, 3), None);
+/// ```
+///
+/// # 計算量
+///
+/// O((V + E) log V)
+pub fn dijkstra_with_prev<G>(graph: &G, start: usize) -> (Vec<Option<G::Cost>>, Vec<Option<usize>>)
+where
+    G: ProvideAdjacencies,
+    G::Cost: Ord + SaturatingAdd + Zero + Copy,
+{
+    let mut dist = vec![None; graph.size()];
+    let mut prev = vec![None; graph.size()];
+    let mut heap = BinaryHeap::new();
+    dist[start] = Some(G::Cost::zero());
+    prev[start] = Some(start);
+    heap.push(Reverse((G::Cost::zero(), start)));
+    while let Some(Reverse((d, v))) = heap.pop() {
+        // より短い距離で既に確定している頂点の古い候補は読み飛ばす。
+        if let Some(best) = dist[v] {
+            if best < d {
+                continue;
+            }
+        }
+
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            let nd = d.saturating_add(edge.cost);
+            let improved = match dist[edge.to] {
+                Some(best) => nd < best,
+                None => true,
+            };
+            if improved {
+                dist[edge.to] = Some(nd);
+                prev[edge.to] = Some(v);
+                heap.push(Reverse((nd, edge.to)));
+            }
+        }
+    }
+
+    (dist, prev)
+}
+
+/// 直前の頂点の配列 `prev` から、始点から `target` までの経路を復元する。
+///
+/// `prev` は `dijkstra_with_prev` が返すものと同じ形式で、始点の直前の頂点は始点自身とする。経路は
+/// 始点と `target` を両端に含む。`target` に到達できなければ `None` を返す。
+///
+/// # 計算量
+///
+/// 経路の長さを L として O(L)
+pub fn reconstruct_path(prev: &[Option<usize>], target: usize) -> Option<Vec<usize>> {
+    let mut path = vec![target];
+    let mut v = target;
+    loop {
+        let p = prev[v]?;
+        if p == v {
+            break;
+        }
+        path.push(p);
+        v = p;
+        assert!(path.len() <= prev.len(), "prev contains a cycle");
+    }
+    path.reverse();
+
+    Some(path)
+}
+
+/// 辺のコストが 0 か 1 のグラフで、`start` から各頂点への最短路の長さを 0-1 BFS で求める。到達で
+/// きない頂点は `None` となる。
+///
+/// コスト 0 の辺で緩和した頂点は両端キューの先頭に、コスト 1 の辺で緩和した頂点は末尾に積む。コス
+/// トが 0 でも 1 でもない辺があれば panic する。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{bfs01, AdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{bfs01, AdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = AdjacencyList::<i64>::of_size(4);
+/// graph.add_edges(vec![(0, 1, 1), (0, 2, 0), (2, 1, 0)]);
+/// assert_eq!(bfs01(&graph, 0), vec![Some(0), Some(0), Some(0), None]);
+/// ```
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn bfs01<G: ProvideAdjacencies<Cost = i64>>(graph: &G, start: usize) -> Vec<Option<i64>> {
+    let mut dist: Vec<Option<i64>> = vec![None; graph.size()];
+    let mut deque = VecDeque::new();
+    dist[start] = Some(0);
+    deque.push_back((0, start));
+    while let Some((d, v)) = deque.pop_front() {
+        // 同じ頂点が複数回積まれることがあるので、古い候補は読み飛ばす。
+        if dist[v] != Some(d) {
+            continue;
+        }
+
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            assert!(
+                edge.cost == 0 || edge.cost == 1,
+                "edge cost must be 0 or 1 but {} -> {} costs {}",
+                edge.from,
+                edge.to,
+                edge.cost
+            );
+
+            let nd = d + edge.cost;
+            let improved = match dist[edge.to] {
+                Some(best) => nd < best,
+                None => true,
+            };
+            if improved {
+                dist[edge.to] = Some(nd);
+                if edge.cost == 0 {
+                    deque.push_front((nd, edge.to));
+                } else {
+                    deque.push_back((nd, edge.to));
+                }
+            }
+        }
+    }
+
+    dist
+}
+
+/// `order` が `order[0]` から始めた幅優先探索で頂点を訪れる順番としてあり得るかを判定する。
+///
+/// 隣接する頂点をどの順番で調べてもよいものとする。`order` は `order[0]` から到達できる頂点をちょ
+/// うど一度ずつ含んでいなければならない。空の `order` に対しては false を返す。
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn is_valid_bfs_order<G: ProvideAdjacencies>(graph: &G, order: &[usize]) -> bool {
+    let n = graph.size();
+    if order.is_empty() || order.iter().any(|&v| v >= n) {
+        return false;
+    }
+
+    // `order` を幅優先探索のキューとみなし、各頂点から新しく見つかる頂点の集合が `order` の続く部分
+    // と一致するかを確かめる。
+    let mut visited = vec![false; n];
+    let mut expected = vec![false; n];
+    visited[order[0]] = true;
+    let mut next = 1;
+    for (i, &v) in order.iter().enumerate() {
+        // キューが空になったのに続きがあるなら、到達できない頂点が含まれている。
+        if next <= i {
+            return false;
+        }
+
+        let mut found = 0;
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            if !visited[edge.to] && !expected[edge.to] {
+                expected[edge.to] = true;
+                found += 1;
+            }
+        }
+
+        if order.len() < next + found {
+            return false;
+        }
+        for &u in &order[next..next + found] {
+            if !expected[u] {
+                return false;
+            }
+            expected[u] = false;
+            visited[u] = true;
+        }
+        next += found;
+    }
+
+    next == order.len()
+}
+
+/// 有向グラフをトポロジカルソートした頂点の列を Kahn 法で求める。
+///
+/// すべての辺 `u -> v` について `u` が `v` より前に来るような頂点の並びを返す。閉路があってそのよう
+/// な並びが存在しなければ `Err(TopoCycleError)` を返す。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{topological_sort, AdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{topological_sort, AdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = AdjacencyList::<i32>::of_size(3);
+/// graph.add_edges(vec![(2, 0), (0, 1)]);
+/// assert_eq!(topological_sort(&graph), Ok(vec![2, 0, 1]));
+/// graph.add_edge((1, 2));
+/// assert!(topological_sort(&graph).is_err());
+/// ```
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn topological_sort<G: ProvideAdjacencies>(graph: &G) -> Result<Vec<usize>, TopoCycleError> {
+    let n = graph.size();
+    let mut indeg = vec![0; n];
+    for v in 0..n {
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            indeg[edge.to] += 1;
+        }
+    }
+
+    let mut queue: VecDeque<_> = (0..n).filter(|&v| indeg[v] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(v) = queue.pop_front() {
+        order.push(v);
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            indeg[edge.to] -= 1;
+            if indeg[edge.to] == 0 {
+                queue.push_back(edge.to);
+            }
+        }
+    }
+
+    // 閉路上の頂点は入次数が 0 にならず、取り出されずに残る。
+    if order.len() == n {
+        Ok(order)
+    } else {
+        Err(TopoCycleError)
+    }
+}
+
+/// 有向グラフの閉路を一つ見つけ、閉路上の頂点を辿る順に返す。閉路がなければ `None` を返す。
+///
+/// 返り値を `[v_0, v_1, ..., v_k]` とすると、`v_0 -> v_1 -> ... -> v_k -> v_0` が閉路になる。自己ル
+/// ープ `v -> v` は長さ 1 の閉路 `[v]` とみなす。再帰を使わずにスタックで深さ優先探索を行い、探索中
+/// の頂点へ戻る辺を見つけたらそこまでのパスを閉路とする。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{find_directed_cycle, AdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{find_directed_cycle, AdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = AdjacencyList::<i32>::of_size(4);
+/// graph.add_edges(vec![(0, 1), (1, 2), (2, 3)]);
+/// assert_eq!(find_directed_cycle(&graph), None);
+/// graph.add_edge((3, 1));
+/// assert_eq!(find_directed_cycle(&graph), Some(vec![1, 2, 3]));
+/// ```
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn find_directed_cycle<G: ProvideAdjacencies>(graph: &G) -> Option<Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Color {
+        /// 未訪問
+        White,
+        /// 探索中 (スタックに積まれている)
+        Gray,
+        /// 探索済み
+        Black,
+    }
+
+    let n = graph.size();
+    let mut color = vec![Color::White; n];
+    for start in 0..n {
+        if color[start] != Color::White {
+            continue;
+        }
+
+        // (頂点, 次に調べる辺の番号)
+        let mut stack = vec![(start, 0)];
+        color[start] = Color::Gray;
+        while let Some(&(v, next)) = stack.last() {
+            let adjacencies = graph
+                .get_adjacencies(v)
+                .expect("vertex index out of bounds");
+            match adjacencies.get(next) {
+                Some(edge) => {
+                    stack.last_mut().unwrap().1 += 1;
+                    let to = edge.to;
+                    match color[to] {
+                        Color::White => {
+                            color[to] = Color::Gray;
+                            stack.push((to, 0));
+                        }
+                        Color::Gray => {
+                            // スタック上の to から v までのパスに v -> to を加えると閉路になる。
+                            let pos = stack
+                                .iter()
+                                .position(|&(u, _)| u == to)
+                                .expect("gray vertex must be on the stack");
+                            return Some(stack[pos..].iter().map(|&(u, _)| u).collect());
+                        }
+                        Color::Black => {}
+                    }
+                }
+                None => {
+                    color[v] = Color::Black;
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// 無向グラフが二部グラフであれば、隣接する頂点が異なる色になるような 2 色の塗り分けを返す。
+///
+/// 連結成分ごとに、番号の最も小さい頂点を `false` として幅優先探索で塗り分ける。奇数長の閉路があっ
+/// て塗り分けられなければ `None` を返す。
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn two_coloring<G: Undirected + ProvideAdjacencies>(graph: &G) -> Option<Vec<bool>> {
+    let n = graph.size();
+    let mut color: Vec<Option<bool>> = vec![None; n];
+    // 連結成分ごとに探索し直すが、色とキューは使い回して O(V + E) に抑える。
+    let mut queue = VecDeque::new();
+    for start in 0..n {
+        if color[start].is_some() {
+            continue;
+        }
+
+        color[start] = Some(false);
+        queue.push_back(start);
+        while let Some(v) = queue.pop_front() {
+            let c = color[v].expect("queued vertex must be colored");
+            for edge in graph
+                .get_adjacencies(v)
+                .expect("vertex index out of bounds")
+            {
+                match color[edge.to] {
+                    None => {
+                        color[edge.to] = Some(!c);
+                        queue.push_back(edge.to);
+                    }
+                    Some(d) if d == c => return None,
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    let color: Vec<bool> = color.into_iter().map(Option::unwrap).collect();
+    Some(color)
+}
+
+/// 無向グラフの最小全域木を Kruskal 法で求め、重みの合計と使った辺のリストを返す。
+///
+/// 連結でなければ、各連結成分の最小全域木を合わせた最小全域森を返す。辺はコストの小さい順に並ぶ。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{kruskal, EdgeList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{kruskal, EdgeList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = EdgeList::of_size(3);
+/// graph.add_edges(vec![(0, 1, 3), (1, 2, 1), (0, 2, 2)]);
+/// let (weight, edges) = kruskal(&graph);
+/// assert_eq!(weight, 3);
+/// assert_eq!(edges.len(), 2);
+/// ```
+///
+/// # 計算量
+///
+/// O(E log E)
+pub fn kruskal<C>(graph: &EdgeList<C>) -> (C, Vec<Edge<C>>)
+where
+    C: Ord + Add<Output = C> + Zero + Copy,
+{
+    let mut edges: Vec<_> = graph.edges().iter().collect();
+    edges.sort_by_key(|e| e.cost);
+
+    let mut uf = DisjointSets::new(graph.size());
+    let mut total = C::zero();
+    let mut chosen = Vec::new();
+    for e in edges {
+        if uf.merge(e.from, e.to) {
+            total = total + e.cost;
+            chosen.push(*e);
+        }
+    }
+
+    (total, chosen)
+}
+
+/// 有向グラフで `root` からすべての頂点に到達できる全域木 (有向全域木) のうち、コストの合計が最小
+/// のものを Chu–Liu/Edmonds 法で求め、そのコストの合計を返す。
+///
+/// 到達できない頂点があれば `None` を返す。自己ループは無視する。`n` は `edges` の頂点数と一致しな
+/// ければならない。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{min_arborescence, EdgeList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{min_arborescence, EdgeList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = EdgeList::of_size(3);
+/// graph.add_edges(vec![(0, 1, 5), (0, 2, 2), (2, 1, 1), (1, 2, 1)]);
+/// assert_eq!(min_arborescence(3, 0, &graph), Some(3));
+/// assert_eq!(min_arborescence(3, 1, &graph), None);
+/// ```
+///
+/// # 計算量
+///
+/// O(VE)
+pub fn min_arborescence(n: usize, root: usize, edges: &EdgeList<i64>) -> Option<i64> {
+    assert_eq!(
+        n,
+        edges.size(),
+        "n does not match the number of vertices of the graph"
+    );
+    assert!(
+        root < n,
+        "root out of range: root is {} but n is {}",
+        root,
+        n
+    );
+
+    let mut n = n;
+    let mut root = root;
+    let mut es: Vec<_> = edges
+        .edges()
+        .iter()
+        .filter(|e| e.from != e.to)
+        .map(|e| (e.from, e.to, e.cost))
+        .collect();
+    let mut total = 0;
+    loop {
+        // 各頂点に入る辺のうち最小のものを選ぶ。
+        let mut min_in: Vec<Option<(i64, usize)>> = vec![None; n];
+        for &(from, to, cost) in &es {
+            let better = match min_in[to] {
+                Some((c, _)) => cost < c,
+                None => true,
+            };
+            if better {
+                min_in[to] = Some((cost, from));
+            }
+        }
+
+        let mut in_cost = vec![0; n];
+        let mut pre = vec![root; n];
+        for v in 0..n {
+            if v == root {
+                continue;
+            }
+            let (cost, from) = min_in[v]?;
+            in_cost[v] = cost;
+            pre[v] = from;
+            total += cost;
+        }
+
+        // 選んだ辺が作る閉路を探し、閉路ごとに一つの頂点に縮約する。
+        let mut id: Vec<Option<usize>> = vec![None; n];
+        let mut visited = vec![None; n];
+        let mut cycles = 0;
+        for v in 0..n {
+            let mut x = v;
+            while x != root && id[x].is_none() && visited[x] != Some(v) {
+                visited[x] = Some(v);
+                x = pre[x];
+            }
+            if x != root && id[x].is_none() {
+                let mut y = pre[x];
+                while y != x {
+                    id[y] = Some(cycles);
+                    y = pre[y];
+                }
+                id[x] = Some(cycles);
+                cycles += 1;
+            }
+        }
+        if cycles == 0 {
+            return Some(total);
+        }
+
+        let mut count = cycles;
+        let id: Vec<usize> = id
+            .into_iter()
+            .map(|c| {
+                c.unwrap_or_else(|| {
+                    count += 1;
+                    count - 1
+                })
+            })
+            .collect();
+
+        // 閉路に入る辺のコストから、閉路内で選んでいた辺のコストを引いておく。
+        es = es
+            .into_iter()
+            .filter(|&(from, to, _)| id[from] != id[to])
+            .map(|(from, to, cost)| (id[from], id[to], cost - in_cost[to]))
+            .collect();
+        n = count;
+        root = id[root];
+    }
+}
+
+/// 無向グラフの全域木の個数を、法 `C::MOD` で求める。
+///
+/// 行列木定理により、ラプラシアン行列から一つの行と列を除いた行列の行列式として求める。辺のコスト
+/// は無視し、多重辺はそれぞれ別の辺として数え、自己ループは無視する。連結でなければ 0 を返す。法は
+/// 素数でなければならない。
+///
+/// ```
+/// # use procon_lib::pcl::math::Modint17 as M;
+/// # use procon_lib::pcl::math::modint::Mod17;
+/// # use procon_lib::pcl::structure::graph::{count_spanning_trees, UndirectedAdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::math::Modint17 as M;
+/// // use crate::pcl::math::modint::Mod17;
+/// // use crate::pcl::structure::graph::{count_spanning_trees, UndirectedAdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// // 完全グラフ K_4 の全域木は 4^2 = 16 個
+/// let mut graph = UndirectedAdjacencyList::of_size(4);
+/// graph.add_edges(vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+/// assert_eq!(count_spanning_trees::<Mod17>(&graph), M::new(16));
+/// ```
+///
+/// # 計算量
+///
+/// O(V^3 + E)
+pub fn count_spanning_trees<C: ModintConst>(graph: &UndirectedAdjacencyList<i64>) -> Modint<C> {
+    let n = graph.size();
+    if n == 0 {
+        return Modint::new(0);
+    }
+
+    // 頂点 n - 1 に対応する行と列を除いたラプラシアン行列
+    let mut laplacian = Matrix::zeros(n - 1, n - 1);
+    for v in 0..n - 1 {
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            if edge.to == v {
+                continue;
+            }
+            laplacian[(v, v)] += Modint::new(1);
+            if edge.to < n - 1 {
+                laplacian[(v, edge.to)] -= Modint::new(1);
+            }
+        }
+    }
+
+    laplacian.determinant()
+}
+
+/// 有向グラフを強連結成分に分解し、各頂点が属する成分の番号を返す。
+///
+/// 成分の番号は Tarjan 法で成分が確定した順に 0 から振られる。これは縮約したグラフのトポロジカル順
+/// の逆順になっており、辺 `u -> v` があれば `u` の成分の番号は `v` の成分の番号以上になる。再帰を使
+/// わずに実装しているので、深いグラフでもスタックオーバーフローしない。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{strongly_connected_components, AdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{strongly_connected_components, AdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = AdjacencyList::<i32>::of_size(4);
+/// graph.add_edges(vec![(0, 1), (1, 0), (1, 2), (2, 3)]);
+/// assert_eq!(strongly_connected_components(&graph), vec![2, 2, 1, 0]);
+/// ```
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn strongly_connected_components<G: ProvideAdjacencies>(graph: &G) -> Vec<usize> {
+    let n = graph.size();
+    // 訪問順の番号と、そこから到達できる未確定の頂点の訪問順の最小値
+    let mut ord = vec![None; n];
+    let mut low = vec![0; n];
+    let mut comp = vec![None; n];
+    // 訪問済みで、まだ成分が確定していない頂点
+    let mut pending = Vec::new();
+    let mut num_visited = 0;
+    let mut num_comps = 0;
+
+    for start in 0..n {
+        if ord[start].is_some() {
+            continue;
+        }
+
+        // (頂点, 次に調べる辺の番号)
+        let mut stack = vec![(start, 0)];
+        ord[start] = Some(num_visited);
+        low[start] = num_visited;
+        num_visited += 1;
+        pending.push(start);
+        while let Some(&(v, next)) = stack.last() {
+            let adjacencies = graph
+                .get_adjacencies(v)
+                .expect("vertex index out of bounds");
+            if let Some(edge) = adjacencies.get(next) {
+                stack.last_mut().unwrap().1 += 1;
+                let to = edge.to;
+                match ord[to] {
+                    None => {
+                        ord[to] = Some(num_visited);
+                        low[to] = num_visited;
+                        num_visited += 1;
+                        pending.push(to);
+                        stack.push((to, 0));
+                    }
+                    Some(o) if comp[to].is_none() => low[v] = low[v].min(o),
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            stack.pop();
+            if let Some(&(parent, _)) = stack.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+
+            // v が成分の根なら、v 以降に積まれた頂点が一つの成分になる。
+            if Some(low[v]) == ord[v] {
+                loop {
+                    let u = pending.pop().expect("v must be pending");
+                    comp[u] = Some(num_comps);
+                    if u == v {
+                        break;
+                    }
+                }
+                num_comps += 1;
+            }
+        }
+    }
+
+    comp.into_iter()
+        .map(|c| c.expect("all vertices are assigned"))
+        .collect()
+}
+
+/// グラフに閉路があり、トポロジカルソートできなかったことを示す。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TopoCycleError;
+
+/// `start` から深さ優先探索を行い、頂点を訪れた順 (行きがけ順) に `visit(頂点, 親)` を呼ぶ。
+///
+/// 再帰を使わずにスタックで実装しているので、深いグラフでもスタックオーバーフローしない。訪れる順
+/// 番は隣接リストの順に再帰で辿った場合と同じになる。
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn dfs_visit<G, F>(graph: &G, start: usize, mut visit: F)
+where
+    G: ProvideAdjacencies,
+    F: FnMut(usize, Option<usize>),
+{
+    let mut visited = vec![false; graph.size()];
+    // (頂点, 次に調べる辺の番号)
+    let mut stack = vec![(start, 0)];
+    visited[start] = true;
+    visit(start, None);
+    while let Some(&(v, next)) = stack.last() {
+        let adjacencies = graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds");
+        match adjacencies.get(next) {
+            Some(edge) => {
+                stack.last_mut().unwrap().1 += 1;
+                if !visited[edge.to] {
+                    visited[edge.to] = true;
+                    visit(edge.to, Some(v));
+                    stack.push((edge.to, 0));
+                }
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::structure::graph::{AdjacencyList, Tree, UndirectedAdjacencyList};
+    use crate::pcl::test_util::XorShift;
+    use crate::pcl::traits::math::graph::{Graph, ReadonlyGraph};
+
+    fn sample_graph() -> UndirectedAdjacencyList<i32> {
+        let mut graph = UndirectedAdjacencyList::of_size(8);
+        graph.add_edges(vec![
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (2, 5),
+            (6, 7),
+        ]);
+        graph
+    }
+
+    #[test]
+    fn test_bfs_visit() {
+        let graph = sample_graph();
+        let mut order = vec![];
+        let mut parents = vec![None; graph.size()];
+        bfs_visit(&graph, 0, |v, p| {
+            order.push(v);
+            parents[v] = p;
+        });
+        assert_eq!(order, vec![0, 1, 2, 3, 5, 4]);
+
+        // 親をたどった深さが訪問順に単調増加し、かつ各頂点の親が実際に隣接していれば BFS 木である。
+        let mut depth = vec![0; graph.size()];
+        for (i, &v) in order.iter().enumerate() {
+            match parents[v] {
+                None => assert_eq!(i, 0),
+                Some(p) => {
+                    assert!(order[..i].contains(&p));
+                    assert!(graph.get_adjacencies(p).unwrap().iter().any(|e| e.to == v));
+                    depth[v] = depth[p] + 1;
+                }
+            }
+        }
+        assert!(order.windows(2).all(|w| depth[w[0]] <= depth[w[1]]));
+        assert_eq!(depth[..6], [0, 1, 1, 2, 3, 2]);
+    }
+
+    #[test]
+    fn test_bfs_dist() {
+        let graph = sample_graph();
+        assert_eq!(
+            bfs_dist(&graph, 0),
+            vec![
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(2),
+                None,
+                None
+            ]
+        );
+        assert_eq!(bfs_dist(&graph, 7)[6], Some(1));
+
+        // 有向グラフでは辺の向きに従う。コストは無視される。
+        let mut graph = AdjacencyList::of_size(4);
+        graph.add_edges(vec![(0, 1, 10), (1, 2, 20), (0, 2, 100), (3, 0, 1)]);
+        assert_eq!(bfs_dist(&graph, 0), vec![Some(0), Some(1), Some(1), None]);
+        assert_eq!(
+            bfs_dist(&graph, 3),
+            vec![Some(1), Some(2), Some(2), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_bfs_dist_tree() {
+        let mut graph = UndirectedAdjacencyList::of_size(6);
+        graph.add_edges(vec![(0, 1, 5), (0, 2, 1), (2, 3, 1), (2, 4, 7), (4, 5, 2)]);
+        let tree = Tree::try_from_graph(graph).unwrap();
+        assert_eq!(
+            bfs_dist(&tree, 0),
+            vec![Some(0), Some(1), Some(1), Some(2), Some(2), Some(3)]
+        );
+        assert_eq!(
+            bfs_dist(&tree, 5),
+            vec![Some(3), Some(4), Some(2), Some(3), Some(1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let mut graph = AdjacencyList::<i64>::of_size(7);
+        graph.add_edges(vec![
+            (0, 1, 7),
+            (0, 2, 9),
+            (0, 5, 14),
+            (1, 2, 10),
+            (1, 3, 15),
+            (2, 3, 11),
+            (2, 5, 2),
+            (3, 4, 6),
+            (5, 4, 9),
+            (4, 0, 1),
+        ]);
+        assert_eq!(
+            dijkstra(&graph, 0),
+            vec![
+                Some(0),
+                Some(7),
+                Some(9),
+                Some(20),
+                Some(20),
+                Some(11),
+                None
+            ]
+        );
+        assert_eq!(
+            dijkstra(&graph, 6),
+            vec![None, None, None, None, None, None, Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_bfs01() {
+        // 3 × 4 のグリッドで、右向きの移動はコスト 0 、下向きの移動はコスト 1 、ただし (1, 0) から
+        // (2, 0) への移動だけは無料とする。
+        let (h, w) = (3, 4);
+        let mut graph = AdjacencyList::<i64>::of_size(h * w);
+        for r in 0..h {
+            for c in 0..w {
+                let v = r * w + c;
+                if c + 1 < w {
+                    graph.add_edge((v, v + 1, 0));
+                }
+                if r + 1 < h {
+                    graph.add_edge((v, v + w, if (r, c) == (1, 0) { 0 } else { 1 }));
+                }
+            }
+        }
+        let dist = bfs01(&graph, 0);
+        assert_eq!(
+            dist,
+            vec![
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+            ]
+        );
+        assert_eq!(bfs01(&graph, 5)[0], None);
+
+        // Dijkstra 法と一致する。
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        for _ in 0..100 {
+            let n = (rng.next_u64() % 15 + 1) as usize;
+            let mut graph = AdjacencyList::<i64>::of_size(n);
+            for _ in 0..rng.next_u64() % 40 {
+                let from = (rng.next_u64() % n as u64) as usize;
+                let to = (rng.next_u64() % n as u64) as usize;
+                graph.add_edge((from, to, (rng.next_u64() & 1) as i64));
+            }
+            assert_eq!(bfs01(&graph, 0), dijkstra(&graph, 0));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bfs01_invalid_cost() {
+        let mut graph = AdjacencyList::<i64>::of_size(2);
+        graph.add_edge((0, 1, 2));
+        bfs01(&graph, 0);
+    }
+
+    #[test]
+    fn test_dijkstra_with_prev() {
+        let mut graph = AdjacencyList::<i64>::of_size(7);
+        graph.add_edges(vec![
+            (0, 1, 7),
+            (0, 2, 9),
+            (0, 5, 14),
+            (1, 2, 10),
+            (1, 3, 15),
+            (2, 3, 11),
+            (2, 5, 2),
+            (3, 4, 6),
+            (5, 4, 9),
+            (4, 0, 1),
+        ]);
+        let (dist, prev) = dijkstra_with_prev(&graph, 0);
+        assert_eq!(dist, dijkstra(&graph, 0));
+        assert_eq!(reconstruct_path(&prev, 4), Some(vec![0, 2, 5, 4]));
+        assert_eq!(reconstruct_path(&prev, 3), Some(vec![0, 2, 3]));
+        assert_eq!(reconstruct_path(&prev, 0), Some(vec![0]));
+        assert_eq!(reconstruct_path(&prev, 6), None);
+
+        // 経路上の辺のコストの和は最短距離に一致する。
+        for (v, &d) in dist.iter().enumerate().take(6) {
+            let path = reconstruct_path(&prev, v).unwrap();
+            let cost: i64 = path
+                .windows(2)
+                .map(|w| {
+                    graph
+                        .get_adjacencies(w[0])
+                        .unwrap()
+                        .iter()
+                        .filter(|e| e.to == w[1])
+                        .map(|e| e.cost)
+                        .min()
+                        .unwrap()
+                })
+                .sum();
+            assert_eq!(Some(cost), d);
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_saturating() {
+        use crate::pcl::traits::utils::num::MaxValue;
+
+        // 値域の最大値に近いコストを足し合わせてもオーバーフローしない。
+        let max = <i64 as MaxValue>::max_value();
+        let mut graph = AdjacencyList::<i64>::of_size(5);
+        graph.add_edges(vec![
+            (0, 1, max - 10),
+            (1, 2, max - 10),
+            (0, 2, max - 1),
+            (0, 3, max - 5),
+            (3, 4, max),
+            (2, 4, 3),
+        ]);
+        let dist = dijkstra(&graph, 0);
+        assert_eq!(
+            dist,
+            vec![
+                Some(0),
+                Some(max - 10),
+                Some(max - 1),
+                Some(max - 5),
+                Some(max)
+            ]
+        );
+        assert!(dist[1] < dist[3] && dist[3] < dist[2] && dist[2] < dist[4]);
+    }
+
+    #[test]
+    fn test_dijkstra_relax_later() {
+        // 頂点 3 は最初に 0 -> 3 (コスト 100) で見つかるが、後から 0 -> 1 -> 2 -> 3 で短縮される。
+        let mut graph = AdjacencyList::<i64>::of_size(5);
+        graph.add_edges(vec![
+            (0, 3, 100),
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 4, 0),
+        ]);
+        assert_eq!(
+            dijkstra(&graph, 0),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(3)]
+        );
+
+        // 無向グラフでも使える。
+        let mut graph = UndirectedAdjacencyList::<i64>::of_size(4);
+        graph.add_edges(vec![(0, 1, 4), (1, 2, 1), (0, 2, 2), (2, 3, 5)]);
+        assert_eq!(
+            dijkstra(&graph, 3),
+            vec![Some(7), Some(6), Some(5), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_is_valid_bfs_order() {
+        let graph = sample_graph();
+        assert!(is_valid_bfs_order(&graph, &[0, 1, 2, 3, 5, 4]));
+        assert!(is_valid_bfs_order(&graph, &[0, 2, 1, 5, 3, 4]));
+        assert!(is_valid_bfs_order(&graph, &[0, 2, 1, 3, 5, 4]));
+        assert!(is_valid_bfs_order(&graph, &[6, 7]));
+
+        // 2 から見つかる 5 より先に、3 から見つかる 4 が来ている。
+        assert!(!is_valid_bfs_order(&graph, &[0, 1, 2, 3, 4, 5]));
+        // 2 を先に展開するので、その次は 2 の子である 3 と 5 でなければならない。
+        assert!(!is_valid_bfs_order(&graph, &[0, 2, 1, 3, 4, 5]));
+        // 深さ優先の順番
+        assert!(!is_valid_bfs_order(&graph, &[0, 1, 3, 2, 5, 4]));
+        // 到達できる頂点が足りない、または到達できない頂点が含まれている。
+        assert!(!is_valid_bfs_order(&graph, &[0, 1, 2, 3, 5]));
+        assert!(!is_valid_bfs_order(&graph, &[0, 1, 2, 3, 5, 4, 6]));
+        assert!(!is_valid_bfs_order(&graph, &[0, 1, 1, 2, 3, 5, 4]));
+        assert!(!is_valid_bfs_order(&graph, &[]));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let mut graph = AdjacencyList::<i32>::of_size(8);
+        let edges = [
+            (5, 0),
+            (5, 2),
+            (0, 1),
+            (2, 1),
+            (1, 3),
+            (4, 3),
+            (6, 4),
+            (6, 7),
+            (2, 3),
+            (0, 1),
+        ];
+        graph.add_edges(edges.iter().cloned());
+
+        let order = topological_sort(&graph).unwrap();
+        let mut pos = [None; 8];
+        for (i, &v) in order.iter().enumerate() {
+            assert_eq!(pos[v], None);
+            pos[v] = Some(i);
+        }
+        assert!(pos.iter().all(Option::is_some));
+        for &(u, v) in &edges {
+            assert!(pos[u] < pos[v], "{} -> {} goes backward", u, v);
+        }
+
+        assert_eq!(
+            topological_sort(&AdjacencyList::<i32>::of_size(0)),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let mut graph = AdjacencyList::<i32>::of_size(5);
+        graph.add_edges(vec![(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert_eq!(topological_sort(&graph), Err(TopoCycleError));
+
+        let mut graph = AdjacencyList::<i32>::of_size(2);
+        graph.add_edge((1, 1));
+        assert_eq!(topological_sort(&graph), Err(TopoCycleError));
+    }
+
+    /// `cycle` が `graph` の閉路になっていることを確かめる。
+    fn assert_directed_cycle(graph: &AdjacencyList<i32>, cycle: &[usize]) {
+        assert!(!cycle.is_empty());
+        let mut seen = vec![false; graph.size()];
+        for (i, &u) in cycle.iter().enumerate() {
+            assert!(!seen[u], "vertex {} appears twice", u);
+            seen[u] = true;
+            let v = cycle[(i + 1) % cycle.len()];
+            assert!(
+                graph.neighbors(u).any(|to| to == v),
+                "no edge {} -> {}",
+                u,
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_directed_cycle_dag() {
+        let mut graph = AdjacencyList::<i32>::of_size(6);
+        graph.add_edges(vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4), (0, 3)]);
+        assert_eq!(find_directed_cycle(&graph), None);
+        assert_eq!(find_directed_cycle(&AdjacencyList::<i32>::of_size(0)), None);
+    }
+
+    #[test]
+    fn test_find_directed_cycle_back_edge() {
+        // 0 -> 1 -> 2 -> 3 -> 1 の後ろ向きの辺で閉路ができる。
+        let mut graph = AdjacencyList::<i32>::of_size(6);
+        graph.add_edges(vec![(0, 1), (1, 2), (2, 3), (3, 4), (3, 1), (5, 0)]);
+        let cycle = find_directed_cycle(&graph).unwrap();
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert_directed_cycle(&graph, &cycle);
+
+        // 探索済みの頂点への辺 (前向きの辺や横断辺) は閉路ではない。
+        let mut graph = AdjacencyList::<i32>::of_size(4);
+        graph.add_edges(vec![(0, 1), (1, 2), (0, 2), (3, 2), (3, 0)]);
+        assert_eq!(find_directed_cycle(&graph), None);
+        graph.add_edge((2, 3));
+        assert_directed_cycle(&graph, &find_directed_cycle(&graph).unwrap());
+    }
+
+    #[test]
+    fn test_find_directed_cycle_self_loop() {
+        let mut graph = AdjacencyList::<i32>::of_size(3);
+        graph.add_edges(vec![(0, 1), (1, 2), (2, 2)]);
+        assert_eq!(find_directed_cycle(&graph), Some(vec![2]));
+    }
+
+    #[test]
+    fn test_find_directed_cycle_agrees_with_topological_sort() {
+        let mut rng = XorShift::new(2_463_534_242);
+
+        for _ in 0..300 {
+            let n = (rng.next_u64() % 8 + 1) as usize;
+            let mut graph = AdjacencyList::<i32>::of_size(n);
+            for _ in 0..rng.next_u64() % 10 {
+                graph.add_edge((
+                    (rng.next_u64() % n as u64) as usize,
+                    (rng.next_u64() % n as u64) as usize,
+                ));
+            }
+
+            match find_directed_cycle(&graph) {
+                Some(cycle) => {
+                    assert_directed_cycle(&graph, &cycle);
+                    assert!(topological_sort(&graph).is_err());
+                }
+                None => assert!(topological_sort(&graph).is_ok()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        // {0, 1, 2} -> {3, 4} -> {5}, {0, 1, 2} -> {6}, {7} は孤立
+        let mut graph = AdjacencyList::<i32>::of_size(8);
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 3),
+            (4, 5),
+            (1, 6),
+            (5, 5),
+        ];
+        graph.add_edges(edges.iter().cloned());
+        let comp = strongly_connected_components(&graph);
+
+        assert_eq!(comp[0], comp[1]);
+        assert_eq!(comp[1], comp[2]);
+        assert_eq!(comp[3], comp[4]);
+        let mut ids = vec![comp[0], comp[3], comp[5], comp[6], comp[7]];
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+        for &(u, v) in &edges {
+            assert!(comp[u] >= comp[v], "{} -> {} goes forward", u, v);
+        }
+    }
+
+    #[test]
+    fn test_strongly_connected_components_dag() {
+        let mut graph = AdjacencyList::<i32>::of_size(6);
+        graph.add_edges(vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4)]);
+        let comp = strongly_connected_components(&graph);
+        let mut ids = comp.clone();
+        ids.sort();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4, 5]);
+        assert!(comp[0] > comp[1] && comp[1] > comp[3] && comp[3] > comp[4]);
+
+        // 再帰で実装するとスタックが溢れるような長い閉路
+        let n = 1_000_000;
+        let mut graph = AdjacencyList::<i32>::of_size(n);
+        graph.add_edges((0..n).map(|v| (v, (v + 1) % n)));
+        assert!(strongly_connected_components(&graph)
+            .iter()
+            .all(|&c| c == 0));
+    }
+
+    #[test]
+    fn test_kruskal() {
+        // 最小全域木の説明でよく使われる 7 頂点のグラフ (A, B, ..., G を 0, 1, ..., 6 とする)
+        let mut graph = EdgeList::of_size(7);
+        graph.add_edges(vec![
+            (0, 1, 7),
+            (0, 3, 5),
+            (1, 2, 8),
+            (1, 3, 9),
+            (1, 4, 7),
+            (2, 4, 5),
+            (3, 4, 15),
+            (3, 5, 6),
+            (4, 5, 8),
+            (4, 6, 9),
+            (5, 6, 11),
+        ]);
+        let (weight, edges) = kruskal(&graph);
+        assert_eq!(weight, 39);
+        assert_eq!(edges.len(), 6);
+        assert_eq!(edges.iter().map(|e| e.cost).sum::<i32>(), weight);
+
+        let mut uf = DisjointSets::new(7);
+        for e in &edges {
+            assert!(uf.merge(e.from, e.to));
+        }
+        assert_eq!(uf.size(), 1);
+    }
+
+    #[test]
+    fn test_kruskal_forest() {
+        let mut graph = EdgeList::of_size(6);
+        graph.add_edges(vec![(0, 1, 4), (1, 2, 1), (0, 2, 2), (3, 4, -3), (4, 3, 1)]);
+        let (weight, edges) = kruskal(&graph);
+        assert_eq!(weight, 0);
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[0], Edge::new(3, 4, -3));
+
+        assert_eq!(kruskal(&EdgeList::<i64>::of_size(3)), (0, vec![]));
+    }
+
+    #[test]
+    fn test_min_arborescence() {
+        // 1 -> 2 -> 3 -> 1 の安い閉路があり、どこか一か所で根からの辺を使う必要がある。
+        let mut graph = EdgeList::of_size(4);
+        graph.add_edges(vec![
+            (0, 1, 10),
+            (0, 2, 12),
+            (0, 3, 11),
+            (1, 2, 1),
+            (2, 3, 2),
+            (3, 1, 3),
+            (2, 2, -5),
+        ]);
+        assert_eq!(min_arborescence(4, 0, &graph), Some(13));
+        // 頂点 1 を根にすると 0 に入る辺がない。
+        assert_eq!(min_arborescence(4, 1, &graph), None);
+
+        let mut graph = EdgeList::of_size(3);
+        graph.add_edge((0, 1, 1));
+        assert_eq!(min_arborescence(3, 0, &graph), None);
+        assert_eq!(min_arborescence(1, 0, &EdgeList::of_size(1)), Some(0));
+    }
+
+    #[test]
+    fn test_min_arborescence_naive() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for _ in 0..300 {
+            let n = (rng.next_u64() % 5 + 1) as usize;
+            let root = (rng.next_u64() % n as u64) as usize;
+            let mut graph = EdgeList::of_size(n);
+            for _ in 0..rng.next_u64() % 10 {
+                let from = (rng.next_u64() % n as u64) as usize;
+                let to = (rng.next_u64() % n as u64) as usize;
+                graph.add_edge((from, to, (rng.next_u64() % 21) as i64 - 5));
+            }
+
+            // 根以外の各頂点に入る辺を一本ずつ選ぶ方法をすべて試す。
+            let incoming: Vec<Vec<_>> = (0..n)
+                .map(|v| {
+                    graph
+                        .edges()
+                        .iter()
+                        .filter(|e| e.to == v && e.from != v)
+                        .collect()
+                })
+                .collect();
+            if (0..n).any(|v| v != root && incoming[v].is_empty()) {
+                assert_eq!(min_arborescence(n, root, &graph), None);
+                continue;
+            }
+
+            let mut best = None;
+            let mut choice = vec![0; n];
+            'outer: loop {
+                let reaches_root = (0..n).all(|v| {
+                    let mut x = v;
+                    for _ in 0..n {
+                        if x == root {
+                            return true;
+                        }
+                        x = incoming[x][choice[x]].from;
+                    }
+                    x == root
+                });
+                if reaches_root {
+                    let cost: i64 = (0..n)
+                        .filter(|&v| v != root)
+                        .map(|v| incoming[v][choice[v]].cost)
+                        .sum();
+                    best = Some(best.map_or(cost, |b: i64| b.min(cost)));
+                }
+
+                for v in (0..n).filter(|&v| v != root) {
+                    choice[v] += 1;
+                    if choice[v] < incoming[v].len() {
+                        continue 'outer;
+                    }
+                    choice[v] = 0;
+                }
+                break;
+            }
+
+            assert_eq!(min_arborescence(n, root, &graph), best);
+        }
+    }
+
+    #[test]
+    fn test_two_coloring() {
+        // 長さ 6 の閉路と、離れた辺、孤立点
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(9);
+        graph.add_edges((0..6).map(|v| (v, (v + 1) % 6)));
+        graph.add_edge((6, 7));
+        let color = two_coloring(&graph).unwrap();
+        assert_eq!(
+            color,
+            vec![false, true, false, true, false, true, false, true, false]
+        );
+
+        // 二部グラフだが頂点の番号順に塗ると途中で色が反転する。
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(4);
+        graph.add_edges(vec![(0, 3), (3, 1), (1, 2)]);
+        let color = two_coloring(&graph).unwrap();
+        for v in 0..4 {
+            for e in graph.get_adjacencies(v).unwrap() {
+                assert_ne!(color[v], color[e.to]);
+            }
+        }
+
+        assert_eq!(
+            two_coloring(&UndirectedAdjacencyList::<i32>::of_size(0)),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_two_coloring_many_components() {
+        // 孤立点が多くても連結成分ごとに O(V) かからない。
+        let n = 1_000_000;
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(n);
+        graph.add_edges(vec![(0, 1), (n - 2, n - 1)]);
+        let color = two_coloring(&graph).unwrap();
+        assert_eq!(color.len(), n);
+        assert!(color[1] && color[n - 1]);
+        assert_eq!(color.iter().filter(|&&c| c).count(), 2);
+
+        graph.add_edges(vec![(n - 1, n - 3), (n - 3, n - 2)]);
+        assert_eq!(two_coloring(&graph), None);
+    }
+
+    #[test]
+    fn test_two_coloring_odd_cycle() {
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(7);
+        graph.add_edges((0..5).map(|v| (v, (v + 1) % 5)));
+        graph.add_edge((5, 6));
+        assert_eq!(two_coloring(&graph), None);
+
+        // 奇数長の閉路が後ろの連結成分にある場合
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(5);
+        graph.add_edges(vec![(0, 1), (2, 3), (3, 4), (4, 2)]);
+        assert_eq!(two_coloring(&graph), None);
+
+        // 自己ループ
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(1);
+        graph.add_edge((0, 0));
+        assert_eq!(two_coloring(&graph), None);
+    }
+
+    #[test]
+    fn test_count_spanning_trees() {
+        use crate::pcl::math::modint::Mod17;
+        use crate::pcl::math::Modint17 as M;
+
+        // 長さ n の閉路の全域木は n 個
+        for n in 3..10 {
+            let mut graph = UndirectedAdjacencyList::of_size(n);
+            graph.add_edges((0..n).map(|v| (v, (v + 1) % n)));
+            assert_eq!(count_spanning_trees::<Mod17>(&graph), M::new(n as i64));
+        }
+
+        // 完全グラフ K_n の全域木は n^(n-2) 個 (Cayley の公式)
+        for n in 1..12 {
+            let mut graph = UndirectedAdjacencyList::of_size(n);
+            for u in 0..n {
+                for v in u + 1..n {
+                    graph.add_edge((u, v));
+                }
+            }
+            assert_eq!(
+                count_spanning_trees::<Mod17>(&graph),
+                M::new(n as i64).pow(n.saturating_sub(2) as u64)
+            );
+        }
+
+        // 多重辺はそれぞれ数え、自己ループは無視する。
+        let mut graph = UndirectedAdjacencyList::of_size(3);
+        graph.add_edges(vec![(0, 1), (0, 1), (1, 2), (2, 2)]);
+        assert_eq!(count_spanning_trees::<Mod17>(&graph), M::new(2));
+
+        // 連結でない
+        let mut graph = UndirectedAdjacencyList::of_size(4);
+        graph.add_edges(vec![(0, 1), (2, 3)]);
+        assert_eq!(count_spanning_trees::<Mod17>(&graph), M::new(0));
+    }
+
+    #[test]
+    fn test_dfs_visit() {
+        let graph = sample_graph();
+        let mut order = vec![];
+        let mut parents = vec![None; graph.size()];
+        dfs_visit(&graph, 0, |v, p| {
+            order.push(v);
+            parents[v] = p;
+        });
+        assert_eq!(order, vec![0, 1, 3, 2, 5, 4]);
+        assert_eq!(
+            parents[..6],
+            [None, Some(0), Some(3), Some(1), Some(5), Some(2)]
+        );
+    }
+
+    #[test]
+    fn test_dfs_visit_deep() {
+        // 再帰で実装するとスタックが溢れるような深さのパス
+        let n = 1_000_000;
+        let mut graph = AdjacencyList::<i32>::of_size(n);
+        graph.add_edges((1..n).map(|v| (v - 1, v)));
+        let mut count = 0;
+        dfs_visit(&graph, 0, |v, p| {
+            assert_eq!(p, v.checked_sub(1));
+            count += 1;
+        });
+        assert_eq!(count, n);
+    }
+}
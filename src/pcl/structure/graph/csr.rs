@@ -0,0 +1,132 @@
+//! CSR (compressed sparse row) 形式のグラフ `CsrGraph` を定義する。
+//!
+//! 隣接リストは頂点ごとに `Vec` を持つため、巨大なグラフではメモリの確保が多くキャッシュ効率も悪い。
+//! CSR 形式ではすべての辺を始点の順に一本の配列に並べ、各頂点の辺がどこから始まるかだけを持つ。一
+//! 度構築したら辺を追加・削除することはできない。
+
+use super::EdgeList;
+use crate::pcl::traits::math::graph::{Edge, ProvideAdjacencies, ReadonlyGraph};
+use crate::{member_name_of, type_name_of};
+use std::fmt;
+
+/// CSR 形式のグラフ。`EdgeList` から構築する。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{CsrGraph, EdgeList};
+/// # use procon_lib::pcl::traits::math::graph::{Graph, ProvideAdjacencies};
+/// // use crate::pcl::structure::graph::{CsrGraph, EdgeList};
+/// // use crate::pcl::traits::math::graph::{Graph, ProvideAdjacencies};
+/// let mut edges = EdgeList::of_size(4);
+/// edges.add_edges(vec![(2, 0, 5), (0, 1, 3), (2, 3, 1)]);
+/// let graph = CsrGraph::from(edges);
+/// assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![0, 3]);
+/// assert_eq!(graph.get_adjacencies(0).unwrap()[0].cost, 3);
+/// assert_eq!(graph.neighbors(3).count(), 0);
+/// ```
+pub struct CsrGraph<C> {
+    /// 頂点 `v` から出る辺は `edges[offsets[v]..offsets[v + 1]]` 。
+    offsets: Vec<usize>,
+    edges: Vec<Edge<C>>,
+}
+
+impl<C> fmt::Debug for CsrGraph<C>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name_of!(CsrGraph<C>))
+            .field(member_name_of!(self.offsets), &self.offsets)
+            .field(member_name_of!(self.edges), &self.edges)
+            .finish()
+    }
+}
+
+impl<C: Clone> Clone for CsrGraph<C> {
+    fn clone(&self) -> Self {
+        Self {
+            offsets: self.offsets.clone(),
+            edges: self.edges.clone(),
+        }
+    }
+}
+
+impl<C> ReadonlyGraph for CsrGraph<C> {
+    type Cost = C;
+
+    fn size(&self) -> usize {
+        self.offsets.len() - 1
+    }
+}
+
+impl<C> ProvideAdjacencies for CsrGraph<C> {
+    fn get_adjacencies(&self, idx: usize) -> Option<&[Edge<C>]> {
+        if idx >= self.size() {
+            return None;
+        }
+
+        Some(&self.edges[self.offsets[idx]..self.offsets[idx + 1]])
+    }
+}
+
+impl<C> From<EdgeList<C>> for CsrGraph<C> {
+    /// 辺リストから構築する。同じ始点の辺は辺リストでの順番を保つ。
+    ///
+    /// # 計算量
+    ///
+    /// O(V + E log E)
+    fn from(edge_list: EdgeList<C>) -> CsrGraph<C> {
+        let n = edge_list.size();
+        let mut edges = edge_list.edges;
+        for edge in &edges {
+            assert!(
+                edge.from < n && edge.to < n,
+                "vertex out of range: edge is {} -> {} but n is {}",
+                edge.from,
+                edge.to,
+                n
+            );
+        }
+
+        // 安定ソートなので、同じ始点の辺の順番は変わらない。
+        edges.sort_by_key(|e| e.from);
+        let mut offsets = vec![0; n + 1];
+        for edge in &edges {
+            offsets[edge.from + 1] += 1;
+        }
+        for v in 0..n {
+            offsets[v + 1] += offsets[v];
+        }
+
+        CsrGraph { offsets, edges }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::structure::graph::AdjacencyList;
+    use crate::pcl::test_util::XorShift;
+    use crate::pcl::traits::math::graph::Graph;
+
+    #[test]
+    fn csr_matches_adjacency_list() {
+        let mut rng = XorShift::new(2_463_534_242);
+
+        for &(n, m) in &[(1, 0), (5, 0), (10, 30), (1000, 5000), (100_000, 300_000)] {
+            let mut edge_list = EdgeList::of_size(n);
+            for _ in 0..m {
+                let from = (rng.next_u64() % n as u64) as usize;
+                let to = (rng.next_u64() % n as u64) as usize;
+                edge_list.add_edge((from, to, rng.next_u64() % 100));
+            }
+
+            let list = AdjacencyList::from(edge_list.clone());
+            let csr = CsrGraph::from(edge_list);
+            assert_eq!(csr.size(), n);
+            for v in 0..n {
+                assert_eq!(csr.get_adjacencies(v), list.get_adjacencies(v));
+            }
+            assert_eq!(csr.get_adjacencies(n), None);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! 最大流とその応用を定義する。
+
+use crate::pcl::traits::math::monoid::Min;
+use crate::pcl::traits::Monoid;
+use std::collections::VecDeque;
+
+/// 残余グラフの辺。
+#[derive(Debug, Clone, Copy)]
+struct FlowEdge {
+    to: usize,
+    cap: i64,
+    /// 逆辺の `graph[to]` 内でのインデックス。
+    rev: usize,
+}
+
+/// Dinic 法で最大流を求める。
+///
+/// 辺を追加すると、容量 0 の逆辺も同時に残余グラフに追加される。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::flow::MaxFlow;
+/// // use crate::pcl::structure::graph::flow::MaxFlow;
+/// let mut flow = MaxFlow::new(4);
+/// flow.add_edge(0, 1, 2);
+/// flow.add_edge(0, 2, 1);
+/// flow.add_edge(1, 2, 1);
+/// flow.add_edge(1, 3, 1);
+/// flow.add_edge(2, 3, 2);
+/// assert_eq!(flow.max_flow(0, 3), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxFlow {
+    graph: Vec<Vec<FlowEdge>>,
+}
+
+impl MaxFlow {
+    /// 頂点数 `n` で辺のないネットワークを生成する。
+    pub fn new(n: usize) -> MaxFlow {
+        MaxFlow {
+            graph: vec![Vec::new(); n],
+        }
+    }
+
+    /// `from` から `to` へ容量 `cap` の辺を追加する。
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64) {
+        let n = self.graph.len();
+        assert!(
+            from < n,
+            "index out of range: from is {} but n is {}",
+            from,
+            n
+        );
+        assert!(to < n, "index out of range: to is {} but n is {}", to, n);
+        assert!(cap >= 0, "capacity must be non-negative");
+
+        let rev_from = self.graph[to].len() + if from == to { 1 } else { 0 };
+        let rev_to = self.graph[from].len();
+        self.graph[from].push(FlowEdge {
+            to,
+            cap,
+            rev: rev_from,
+        });
+        self.graph[to].push(FlowEdge {
+            to: from,
+            cap: 0,
+            rev: rev_to,
+        });
+    }
+
+    /// `s` から `t` への最大流を求める。残余グラフは流した後の状態に更新される。
+    ///
+    /// 同じ頂点間に複数の辺があってもよい。`s == t` のときは 0 を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(V^2 E)
+    pub fn max_flow(&mut self, s: usize, t: usize) -> i64 {
+        let n = self.graph.len();
+        assert!(s < n, "index out of range: s is {} but n is {}", s, n);
+        assert!(t < n, "index out of range: t is {} but n is {}", t, n);
+        if s == t {
+            return 0;
+        }
+
+        let mut flow = 0;
+        loop {
+            let level = self.levels(s);
+            if level[t] < 0 {
+                return flow;
+            }
+
+            let mut iter = vec![0; n];
+            loop {
+                let f = self.augment(s, t, Min::<i64>::id().0, &level, &mut iter);
+                if f == 0 {
+                    break;
+                }
+                flow += f;
+            }
+        }
+    }
+
+    /// `s` から残余グラフ上で幅優先探索を行い、各頂点の距離 (到達できなければ -1) を求める。
+    fn levels(&self, s: usize) -> Vec<i64> {
+        let mut level = vec![-1; self.graph.len()];
+        let mut queue = VecDeque::new();
+        level[s] = 0;
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            for e in &self.graph[v] {
+                if e.cap > 0 && level[e.to] < 0 {
+                    level[e.to] = level[v] + 1;
+                    queue.push_back(e.to);
+                }
+            }
+        }
+
+        level
+    }
+
+    /// レベルグラフ上で `v` から `t` へ高々 `limit` だけ流せるパスを探して流し、流せた量を返す。
+    fn augment(
+        &mut self,
+        v: usize,
+        t: usize,
+        limit: i64,
+        level: &[i64],
+        iter: &mut [usize],
+    ) -> i64 {
+        if v == t {
+            return limit;
+        }
+
+        while iter[v] < self.graph[v].len() {
+            let e = self.graph[v][iter[v]];
+            if e.cap > 0 && level[v] < level[e.to] {
+                let d = self.augment(e.to, t, limit.min(e.cap), level, iter);
+                if d > 0 {
+                    self.graph[v][iter[v]].cap -= d;
+                    self.graph[e.to][e.rev].cap += d;
+                    return d;
+                }
+            }
+            iter[v] += 1;
+        }
+
+        0
+    }
+}
+
+/// 左側 `left` 頂点、右側 `right` 頂点の二部グラフの最大マッチングの大きさを求める。
+///
+/// `edges` の各要素 `(l, r)` は左側の頂点 `l` と右側の頂点 `r` を結ぶ辺を表す。始点から左側、左側か
+/// ら右側、右側から終点へ容量 1 の辺を張ったネットワークの最大流を Dinic 法で求める。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::flow::bipartite_matching_flow;
+/// // use crate::pcl::structure::graph::flow::bipartite_matching_flow;
+/// let edges = [(0, 0), (0, 1), (1, 0), (2, 0)];
+/// assert_eq!(bipartite_matching_flow(3, 2, &edges), 2);
+/// ```
+///
+/// # 計算量
+///
+/// O(E sqrt(V))
+pub fn bipartite_matching_flow(left: usize, right: usize, edges: &[(usize, usize)]) -> usize {
+    let source = left + right;
+    let sink = source + 1;
+    let mut flow = MaxFlow::new(left + right + 2);
+    for l in 0..left {
+        flow.add_edge(source, l, 1);
+    }
+    for r in 0..right {
+        flow.add_edge(left + r, sink, 1);
+    }
+    for &(l, r) in edges {
+        assert!(
+            l < left,
+            "index out of range: l is {} but left is {}",
+            l,
+            left
+        );
+        assert!(
+            r < right,
+            "index out of range: r is {} but right is {}",
+            r,
+            right
+        );
+        flow.add_edge(l, left + r, 1);
+    }
+
+    flow.max_flow(source, sink) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+
+    /// Kuhn 法 (増加路を一本ずつ探す方法) による最大マッチングの大きさ。
+    fn kuhn(left: usize, right: usize, edges: &[(usize, usize)]) -> usize {
+        fn try_augment(
+            l: usize,
+            adj: &[Vec<usize>],
+            used: &mut [bool],
+            matched: &mut [Option<usize>],
+        ) -> bool {
+            for &r in &adj[l] {
+                if used[r] {
+                    continue;
+                }
+                used[r] = true;
+                let ok = match matched[r] {
+                    Some(m) => try_augment(m, adj, used, matched),
+                    None => true,
+                };
+                if ok {
+                    matched[r] = Some(l);
+                    return true;
+                }
+            }
+
+            false
+        }
+
+        let mut adj = vec![Vec::new(); left];
+        for &(l, r) in edges {
+            adj[l].push(r);
+        }
+
+        let mut matched = vec![None; right];
+        let mut res = 0;
+        for l in 0..left {
+            let mut used = vec![false; right];
+            if try_augment(l, &adj, &mut used, &mut matched) {
+                res += 1;
+            }
+        }
+
+        res
+    }
+
+    #[test]
+    fn max_flow_classic() {
+        // 最大流の説明でよく使われる 6 頂点のネットワーク (最大流 23)
+        let mut flow = MaxFlow::new(6);
+        flow.add_edge(0, 1, 16);
+        flow.add_edge(0, 2, 13);
+        flow.add_edge(1, 2, 10);
+        flow.add_edge(2, 1, 4);
+        flow.add_edge(1, 3, 12);
+        flow.add_edge(3, 2, 9);
+        flow.add_edge(2, 4, 14);
+        flow.add_edge(4, 3, 7);
+        flow.add_edge(3, 5, 20);
+        flow.add_edge(4, 5, 4);
+        assert_eq!(flow.max_flow(0, 5), 23);
+        // 流した後の残余グラフにはもう増加路がない。
+        assert_eq!(flow.max_flow(0, 5), 0);
+    }
+
+    #[test]
+    fn max_flow_multiple_phases() {
+        // 最初のレベルグラフでは短い経路しか使えず、逆辺を使う長い経路は次の段階で見つかる。
+        let mut flow = MaxFlow::new(6);
+        flow.add_edge(0, 1, 1);
+        flow.add_edge(0, 2, 1);
+        flow.add_edge(1, 3, 1);
+        flow.add_edge(1, 4, 1);
+        flow.add_edge(2, 3, 1);
+        flow.add_edge(3, 5, 1);
+        flow.add_edge(4, 5, 1);
+        assert_eq!(flow.max_flow(0, 5), 2);
+
+        // 多重辺と自己ループ
+        let mut flow = MaxFlow::new(3);
+        flow.add_edge(0, 1, 3);
+        flow.add_edge(0, 1, 4);
+        flow.add_edge(1, 1, 100);
+        flow.add_edge(1, 2, 5);
+        flow.add_edge(1, 2, 1);
+        assert_eq!(flow.max_flow(0, 2), 6);
+    }
+
+    #[test]
+    fn max_flow_trivial() {
+        let mut flow = MaxFlow::new(3);
+        flow.add_edge(0, 1, 5);
+        assert_eq!(flow.max_flow(0, 0), 0);
+        assert_eq!(flow.max_flow(0, 2), 0);
+        assert_eq!(flow.max_flow(1, 0), 0);
+        assert_eq!(flow.max_flow(0, 1), 5);
+    }
+
+    #[test]
+    fn bipartite_matching_agrees_with_kuhn() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for _ in 0..200 {
+            let left = (rng.next_u64() % 8) as usize;
+            let right = (rng.next_u64() % 8) as usize;
+            let mut edges = Vec::new();
+            if left > 0 && right > 0 {
+                for _ in 0..rng.next_u64() % 20 {
+                    edges.push((
+                        (rng.next_u64() % left as u64) as usize,
+                        (rng.next_u64() % right as u64) as usize,
+                    ));
+                }
+            }
+
+            assert_eq!(
+                bipartite_matching_flow(left, right, &edges),
+                kuhn(left, right, &edges)
+            );
+        }
+    }
+
+    #[test]
+    fn bipartite_matching_complete() {
+        let mut edges = Vec::new();
+        for l in 0..5 {
+            for r in 0..7 {
+                edges.push((l, r));
+            }
+        }
+        assert_eq!(bipartite_matching_flow(5, 7, &edges), 5);
+        assert_eq!(bipartite_matching_flow(5, 7, &[]), 0);
+    }
+}
@@ -0,0 +1,122 @@
+//! 迷路などの二次元のマップをグラフに変換する関数を定義する。
+//!
+//! `H × W` のマップの `r` 行 `c` 列のマスを頂点 `r * W + c` に対応させる。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::graph::grid::{grid_graph, idx};
+//! # use procon_lib::pcl::structure::graph::bfs_dist;
+//! // use crate::pcl::structure::graph::grid::{grid_graph, idx};
+//! // use crate::pcl::structure::graph::bfs_dist;
+//! let map: Vec<Vec<char>> = ["..#", "#..", "..."].iter().map(|s| s.chars().collect()).collect();
+//! let graph = grid_graph(&map, |c| c == '.');
+//! let dist = bfs_dist(&graph, idx(0, 0, 3));
+//! assert_eq!(dist[idx(2, 0, 3)], Some(4));
+//! assert_eq!(dist[idx(0, 2, 3)], None);
+//! ```
+
+use super::UndirectedAdjacencyList;
+use crate::pcl::traits::math::graph::{Edge, Graph};
+
+/// 幅 `w` のマップの `r` 行 `c` 列のマスに対応する頂点の番号を返す。
+pub fn idx(r: usize, c: usize, w: usize) -> usize {
+    r * w + c
+}
+
+/// マップ `map` から、通れるマス同士が上下左右に隣接していれば重み 1 の辺で結んだ無向グラフを作る。
+///
+/// マスが通れるかどうかは `passable` で判定する。通れないマスも頂点としては存在するが、辺を持たな
+/// い。すべての行の長さは等しくなければならない。
+///
+/// # 計算量
+///
+/// O(HW)
+pub fn grid_graph<F>(map: &[Vec<char>], passable: F) -> UndirectedAdjacencyList<i32>
+where
+    F: Fn(char) -> bool,
+{
+    let h = map.len();
+    let w = map.first().map_or(0, |row| row.len());
+    assert!(
+        map.iter().all(|row| row.len() == w),
+        "all rows must have the same length"
+    );
+
+    let mut graph = UndirectedAdjacencyList::of_size(h * w);
+    for r in 0..h {
+        for c in 0..w {
+            if !passable(map[r][c]) {
+                continue;
+            }
+            if r + 1 < h && passable(map[r + 1][c]) {
+                graph.add_edge(Edge::one(idx(r, c, w), idx(r + 1, c, w)));
+            }
+            if c + 1 < w && passable(map[r][c + 1]) {
+                graph.add_edge(Edge::one(idx(r, c, w), idx(r, c + 1, w)));
+            }
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::traits::math::graph::{ProvideAdjacencies, ReadonlyGraph};
+
+    fn parse(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|s| s.chars().collect()).collect()
+    }
+
+    fn sorted_neighbors(graph: &UndirectedAdjacencyList<i32>, v: usize) -> Vec<usize> {
+        let mut res: Vec<_> = graph.neighbors(v).collect();
+        res.sort();
+        res
+    }
+
+    #[test]
+    fn grid_graph_maze() {
+        // S.#.
+        // .##.
+        // ....
+        let map = parse(&["S.#.", ".##.", "...."]);
+        let w = 4;
+        let graph = grid_graph(&map, |c| c != '#');
+        assert_eq!(graph.size(), 12);
+
+        assert_eq!(sorted_neighbors(&graph, idx(0, 0, w)), vec![1, 4]);
+        assert_eq!(sorted_neighbors(&graph, idx(0, 1, w)), vec![0]);
+        assert_eq!(sorted_neighbors(&graph, idx(0, 3, w)), vec![7]);
+        assert_eq!(sorted_neighbors(&graph, idx(2, 1, w)), vec![8, 10]);
+        assert_eq!(sorted_neighbors(&graph, idx(2, 3, w)), vec![7, 10]);
+
+        // 壁には辺がない。
+        for &(r, c) in &[(0, 2), (1, 1), (1, 2)] {
+            assert!(graph.get_adjacencies(idx(r, c, w)).unwrap().is_empty());
+        }
+        for v in 0..12 {
+            for e in graph.get_adjacencies(v).unwrap() {
+                assert_eq!(e.cost, 1);
+                let (r, c) = (e.to / w, e.to % w);
+                assert_ne!(map[r][c], '#');
+            }
+        }
+
+        // 辺の本数 (各辺は両方向に現れる)
+        let total: usize = (0..12)
+            .map(|v| graph.get_adjacencies(v).unwrap().len())
+            .sum();
+        assert_eq!(total, 2 * 8);
+    }
+
+    #[test]
+    fn grid_graph_degenerate() {
+        assert_eq!(grid_graph(&[], |_| true).size(), 0);
+
+        let graph = grid_graph(&parse(&["...."]), |c| c == '.');
+        assert_eq!(sorted_neighbors(&graph, 1), vec![0, 2]);
+        assert_eq!(idx(3, 2, 5), 17);
+    }
+}
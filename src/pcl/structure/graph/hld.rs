@@ -0,0 +1,273 @@
+//! 木の重軽分解 (heavy–light decomposition) `Hld` を定義する。
+//!
+//! 頂点を一列に並べ直し、木の上の任意のパスを O(log n) 個の連続した区間に分解する。並べ直した位置
+//! にセグメント木などを載せれば、パス上の和や最大値を求められる。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::graph::{Hld, Tree};
+//! # use procon_lib::pcl::structure::SegmentTree;
+//! # use procon_lib::pcl::traits::math::group::Additive as A;
+//! // use crate::pcl::structure::graph::{Hld, Tree};
+//! // use crate::pcl::structure::SegmentTree;
+//! // use crate::pcl::traits::math::group::Additive as A;
+//! //     0
+//! //    / \
+//! //   1   2
+//! //  / \
+//! // 3   4
+//! let tree = Tree::from_parent_array(&[None, Some(0), Some(0), Some(1), Some(1)]).unwrap();
+//! let hld = Hld::new(&tree, 0);
+//! let weight = [1, 10, 100, 1000, 10000];
+//! let mut values = vec![A(0); 5];
+//! for v in 0..5 {
+//!     values[hld.position(v)] = A(weight[v]);
+//! }
+//! let st = SegmentTree::from_array(values);
+//!
+//! let path_sum = |u, v| -> i64 { hld.path_ranges(u, v).into_iter().map(|r| st.query(r).0).sum() };
+//! assert_eq!(path_sum(3, 2), 1111);
+//! assert_eq!(path_sum(3, 4), 11010);
+//! ```
+
+use super::tree::rooting;
+use super::Tree;
+use crate::pcl::traits::math::graph::{ProvideAdjacencies, ReadonlyGraph};
+use std::mem::swap;
+use std::ops::Range;
+
+/// 木の重軽分解。
+///
+/// 各頂点について、部分木が最も大きい子 (重い子) への辺を重い辺とし、重い辺でつながった頂点の列
+/// (重いパス) が連続した位置に並ぶようにする。
+#[derive(Debug, Clone)]
+pub struct Hld {
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    /// 頂点が属する重いパスの、最も根に近い頂点。
+    head: Vec<usize>,
+    position: Vec<usize>,
+}
+
+impl Hld {
+    /// `tree` を `root` を根とする根付き木とみなして分解する。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn new<C>(tree: &Tree<C>, root: usize) -> Hld {
+        let n = tree.size();
+        let rooted = rooting(tree, root);
+        let parent = rooted.parent;
+        let size = rooted.subtree_size;
+
+        // 重い子を最後に積むと、次に取り出されて親のすぐ後ろの位置に並ぶ。
+        let mut head = vec![root; n];
+        let mut position = vec![0; n];
+        let mut stack = vec![root];
+        let mut next = 0;
+        while let Some(v) = stack.pop() {
+            position[v] = next;
+            next += 1;
+
+            let children = tree
+                .get_adjacencies(v)
+                .expect("vertex index out of bounds")
+                .iter()
+                .map(|e| e.to)
+                .filter(|&c| Some(c) != parent[v]);
+            let heavy = children.clone().max_by_key(|&c| size[c]);
+            for c in children {
+                if Some(c) != heavy {
+                    head[c] = c;
+                    stack.push(c);
+                }
+            }
+            if let Some(h) = heavy {
+                head[h] = head[v];
+                stack.push(h);
+            }
+        }
+
+        Hld {
+            parent,
+            depth: rooted.depth,
+            head,
+            position,
+        }
+    }
+
+    /// 頂点 `v` の並べ直した後の位置を返す。
+    pub fn position(&self, v: usize) -> usize {
+        self.position[v]
+    }
+
+    /// 各頂点の並べ直した後の位置の配列を返す。
+    pub fn positions(&self) -> &[usize] {
+        &self.position
+    }
+
+    /// 頂点 `u` と `v` の最小共通祖先を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] < self.depth[self.head[v]] {
+                swap(&mut u, &mut v);
+            }
+            u = self.parent[self.head[u]].expect("head of a non-root path has a parent");
+        }
+
+        if self.depth[u] < self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    /// `u` から `v` へのパス上の頂点 (両端を含む) の位置を、互いに交わらない区間の列として返す。区
+    /// 間の順番は特に定めない。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn path_ranges(&self, u: usize, v: usize) -> Vec<Range<usize>> {
+        self.ranges(u, v, false)
+    }
+
+    /// `u` から `v` へのパス上の辺の位置を、互いに交わらない区間の列として返す。
+    ///
+    /// 辺の重みは、その辺の子の側の頂点の位置に置くものとする。つまり、最小共通祖先の位置を除いた
+    /// `path_ranges` と同じ区間になる。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn path_ranges_edges(&self, u: usize, v: usize) -> Vec<Range<usize>> {
+        self.ranges(u, v, true)
+    }
+
+    fn ranges(&self, mut u: usize, mut v: usize, edge: bool) -> Vec<Range<usize>> {
+        let mut res = Vec::new();
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] < self.depth[self.head[v]] {
+                swap(&mut u, &mut v);
+            }
+            let h = self.head[u];
+            res.push(self.position[h]..self.position[u] + 1);
+            u = self.parent[h].expect("head of a non-root path has a parent");
+        }
+
+        // 同じ重いパス上では、浅い方の頂点が最小共通祖先になる。
+        let (lo, hi) = if self.position[u] <= self.position[v] {
+            (self.position[u], self.position[v])
+        } else {
+            (self.position[v], self.position[u])
+        };
+        let lo = if edge { lo + 1 } else { lo };
+        if lo <= hi {
+            res.push(lo..hi + 1);
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+
+    /// 素朴にパス上の頂点を求める。
+    fn path_vertices(parent: &[Option<usize>], depth: &[usize], u: usize, v: usize) -> Vec<usize> {
+        let (mut u, mut v) = (u, v);
+        let mut res = Vec::new();
+        while u != v {
+            if depth[u] < depth[v] {
+                swap(&mut u, &mut v);
+            }
+            res.push(u);
+            u = parent[u].unwrap();
+        }
+        res.push(u);
+        res
+    }
+
+    #[test]
+    fn hld_ranges_cover_path() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for n in 1..60 {
+            let parents: Vec<_> = (0..n)
+                .map(|v| {
+                    if v == 0 {
+                        None
+                    } else {
+                        Some((rng.next_u64() % v as u64) as usize)
+                    }
+                })
+                .collect();
+            let tree = Tree::from_parent_array(&parents).unwrap();
+            let root = (rng.next_u64() % n as u64) as usize;
+            let hld = Hld::new(&tree, root);
+            let rooted = rooting(&tree, root);
+
+            // 位置は 0..n の並べ替えになっている。
+            let mut at = vec![None; n];
+            for v in 0..n {
+                assert_eq!(at[hld.position(v)], None);
+                at[hld.position(v)] = Some(v);
+            }
+            assert_eq!(hld.positions().len(), n);
+
+            for _ in 0..30 {
+                let u = (rng.next_u64() % n as u64) as usize;
+                let v = (rng.next_u64() % n as u64) as usize;
+                let mut expected = path_vertices(&rooted.parent, &rooted.depth, u, v);
+                let lca = *expected.iter().min_by_key(|&&x| rooted.depth[x]).unwrap();
+                assert_eq!(hld.lca(u, v), lca);
+
+                let covered = |ranges: Vec<Range<usize>>| {
+                    let mut res: Vec<_> = ranges
+                        .into_iter()
+                        .flat_map(|r| r.map(|p| at[p].unwrap()))
+                        .collect();
+                    res.sort();
+                    res
+                };
+                expected.sort();
+                assert_eq!(covered(hld.path_ranges(u, v)), expected);
+
+                // 辺の場合は最小共通祖先を除いた頂点 (その頂点と親を結ぶ辺) になる。
+                expected.retain(|&x| x != lca);
+                assert_eq!(covered(hld.path_ranges_edges(u, v)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn hld_path_has_few_ranges() {
+        // 完全二分木では、どのパスも O(log n) 個の区間に分かれる。
+        let n = 1023;
+        let parents: Vec<_> = (0..n)
+            .map(|v: usize| v.checked_sub(1).map(|p| p / 2))
+            .collect();
+        let tree = Tree::from_parent_array(&parents).unwrap();
+        let hld = Hld::new(&tree, 0);
+        for u in (0..n).step_by(37) {
+            for v in (0..n).step_by(41) {
+                assert!(hld.path_ranges(u, v).len() <= 2 * 10 + 1);
+            }
+        }
+
+        // パスは一つの区間になる。
+        let parents: Vec<_> = (0..100).map(|v: usize| v.checked_sub(1)).collect();
+        let hld = Hld::new(&Tree::from_parent_array(&parents).unwrap(), 0);
+        assert_eq!(hld.path_ranges(99, 10), vec![10..100]);
+        assert_eq!(hld.path_ranges_edges(99, 10), vec![11..100]);
+        assert!(hld.path_ranges_edges(5, 5).is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+//! 根付き木の最小共通祖先 (LCA) をダブリングで求める `LcaTree` を定義する。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::graph::{LcaTree, Tree, UndirectedAdjacencyList};
+//! # use procon_lib::pcl::traits::math::graph::Graph;
+//! // use crate::pcl::structure::graph::{LcaTree, Tree, UndirectedAdjacencyList};
+//! // use crate::pcl::traits::math::graph::Graph;
+//! let mut graph = UndirectedAdjacencyList::<i32>::of_size(5);
+//! graph.add_edges(vec![(0, 1), (0, 2), (1, 3), (1, 4)]);
+//! let tree = Tree::try_from_graph(graph).unwrap();
+//! let lca = LcaTree::new(&tree, 0);
+//! assert_eq!(lca.lca(3, 4), 1);
+//! assert_eq!(lca.lca(3, 2), 0);
+//! assert_eq!(lca.depth(4), 2);
+//! ```
+
+use super::Tree;
+use crate::pcl::traits::math::graph::{ProvideAdjacencies, ReadonlyGraph};
+use std::mem::swap;
+
+/// 根付き木の最小共通祖先を求める。
+///
+/// 各頂点の 2^k 個上の祖先の表を前計算しておく。
+#[derive(Debug, Clone)]
+pub struct LcaTree {
+    root: usize,
+    /// `up[k][v]` は `v` の 2^k 個上の祖先。そのような祖先がなければ根。
+    up: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+    /// `tin[v]` は根からの深さ優先探索で `v` を訪れた順番。
+    tin: Vec<usize>,
+}
+
+impl LcaTree {
+    /// `tree` を `root` を根とする根付き木とみなして前計算を行う。
+    ///
+    /// # 計算量
+    ///
+    /// O(n log n)
+    pub fn new<C>(tree: &Tree<C>, root: usize) -> LcaTree {
+        let n = tree.size();
+        assert!(
+            root < n,
+            "root out of range: root is {} but n is {}",
+            root,
+            n
+        );
+
+        // 根から深さ優先探索をして親と深さ、訪れた順番を求める。
+        let mut parent = vec![root; n];
+        let mut depth = vec![0; n];
+        let mut tin = vec![0; n];
+        let mut visited = vec![false; n];
+        let mut stack = vec![root];
+        let mut time = 0;
+        visited[root] = true;
+        while let Some(v) = stack.pop() {
+            tin[v] = time;
+            time += 1;
+            for edge in tree.get_adjacencies(v).expect("vertex index out of bounds") {
+                if visited[edge.to] {
+                    continue;
+                }
+                visited[edge.to] = true;
+                parent[edge.to] = v;
+                depth[edge.to] = depth[v] + 1;
+                stack.push(edge.to);
+            }
+        }
+
+        let mut log = 1;
+        while (1 << log) < n {
+            log += 1;
+        }
+        let mut up = Vec::with_capacity(log);
+        up.push(parent);
+        for k in 1..log {
+            let next: Vec<_> = (0..n).map(|v| up[k - 1][up[k - 1][v]]).collect();
+            up.push(next);
+        }
+
+        LcaTree {
+            root,
+            up,
+            depth,
+            tin,
+        }
+    }
+
+    /// 根を返す。
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// 頂点 `v` の深さ (根からの辺の本数) を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// 根からの深さ優先探索で頂点 `v` を訪れた順番 (0-indexed) を返す。
+    ///
+    /// 各部分木の頂点はこの順番で連続した区間になる。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn tin(&self, v: usize) -> usize {
+        self.tin[v]
+    }
+
+    /// 頂点 `u` と `v` の最小共通祖先を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        if self.depth[u] < self.depth[v] {
+            swap(&mut u, &mut v);
+        }
+
+        // 深い方を同じ深さまで引き上げる。
+        let diff = self.depth[u] - self.depth[v];
+        for (k, up) in self.up.iter().enumerate() {
+            if diff >> k & 1 != 0 {
+                u = up[u];
+            }
+        }
+        if u == v {
+            return u;
+        }
+
+        // 祖先が一致しない範囲でできるだけ上に登ると、そのすぐ上が最小共通祖先になる。
+        for up in self.up.iter().rev() {
+            if up[u] != up[v] {
+                u = up[u];
+                v = up[v];
+            }
+        }
+
+        self.up[0][u]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::structure::graph::UndirectedAdjacencyList;
+    use crate::pcl::traits::math::graph::Graph;
+
+    //           0
+    //        /  |  \
+    //       1   2   3
+    //      / \      |
+    //     4   5     6
+    //    /         / \
+    //   7         8   9
+    fn sample_tree() -> Tree<i32> {
+        let mut graph = UndirectedAdjacencyList::of_size(10);
+        graph.add_edges(vec![
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 4),
+            (1, 5),
+            (3, 6),
+            (4, 7),
+            (6, 8),
+            (6, 9),
+        ]);
+        Tree::try_from_graph(graph).unwrap()
+    }
+
+    #[test]
+    fn lca_tree() {
+        let lca = LcaTree::new(&sample_tree(), 0);
+        assert_eq!(lca.root(), 0);
+        assert_eq!(
+            (0..10).map(|v| lca.depth(v)).collect::<Vec<_>>(),
+            vec![0, 1, 1, 1, 2, 2, 2, 3, 3, 3]
+        );
+
+        // 行きがけ順では部分木が連続した区間になり、親は子より先に来る。
+        assert_eq!(lca.tin(0), 0);
+        let mut order: Vec<_> = (0..10).collect();
+        order.sort_by_key(|&v| lca.tin(v));
+        assert!((1..10).all(|v| lca.tin(lca.up[0][v]) < lca.tin(v)));
+        let subtree: Vec<_> = order.iter().map(|&v| lca.lca(v, 1) == 1).collect();
+        let first = subtree.iter().position(|&b| b).unwrap();
+        assert!(subtree[first..first + 4].iter().all(|&b| b));
+        assert_eq!(subtree.iter().filter(|&&b| b).count(), 4);
+
+        assert_eq!(lca.lca(7, 5), 1);
+        assert_eq!(lca.lca(8, 9), 6);
+        assert_eq!(lca.lca(7, 9), 0);
+        assert_eq!(lca.lca(2, 5), 0);
+        // 祖先と子孫
+        assert_eq!(lca.lca(1, 7), 1);
+        assert_eq!(lca.lca(9, 3), 3);
+        assert_eq!(lca.lca(0, 8), 0);
+        // 根や同じ頂点
+        assert_eq!(lca.lca(0, 0), 0);
+        assert_eq!(lca.lca(4, 4), 4);
+    }
+
+    #[test]
+    fn lca_tree_other_root() {
+        let lca = LcaTree::new(&sample_tree(), 6);
+        assert_eq!(lca.depth(6), 0);
+        assert_eq!(lca.depth(7), 5);
+        assert_eq!(lca.lca(7, 2), 0);
+        assert_eq!(lca.lca(8, 9), 6);
+        assert_eq!(lca.lca(4, 5), 1);
+        assert_eq!(lca.lca(8, 0), 6);
+    }
+
+    #[test]
+    fn lca_tree_path() {
+        // パスや単一頂点の木も扱える。
+        let n: usize = 1000;
+        let parents: Vec<_> = (0..n).map(|v| v.checked_sub(1)).collect();
+        let lca = LcaTree::new(&Tree::from_parent_array(&parents).unwrap(), 0);
+        assert_eq!(lca.lca(n - 1, 345), 345);
+        assert_eq!(lca.lca(345, 678), 345);
+        assert_eq!(lca.depth(n - 1), n - 1);
+
+        let single = LcaTree::new(&Tree::from_parent_array(&[None]).unwrap(), 0);
+        assert_eq!(single.lca(0, 0), 0);
+    }
+}
@@ -0,0 +1,966 @@
+//! 様々な表現のグラフやグラフアルゴリズムを定義する。
+//!
+//! # Examples
+//!
+//! グラフの表現には様々なものがあり、それぞれ得意不得意がある。最も基本となるのはおそらく隣接リスト
+//! 形式のグラフだろう。いずれにせよ、すべてのグラフは `add_edge()` や `remove_edge()` 関数により辺
+//! を追加したり削除したりできるようになっており、内部実装については気にしなくても扱えるように作られ
+//! ている。
+//!
+//! 例えば、`n` 頂点のグラフで辺のリストが `edges` で与えられている場合、このグラフを保持する無向隣
+//! 接グラフを生成するには次のようにかけば良い。
+//!
+//! ```rust
+//! # use procon_lib::pcl::structure::graph::AdjacencyList;
+//! # use procon_lib::pcl::traits::math::graph::{Edge, Graph};
+//! // use crate::pcl::structure::graph::AdjacencyList;
+//! // use crate::pcl::traits::math::graph::{Edge, Graph};
+//! let n = 9;
+//! let edges = vec![
+//!     Edge::new(0, 2, 1),
+//!     Edge::new(0, 3, 1),
+//!     Edge::new(1, 4, 1),
+//!     Edge::new(1, 5, 1),
+//!     Edge::new(1, 6, 1),
+//!     Edge::new(2, 7, 1),
+//!     Edge::new(2, 8, 1)
+//! ];
+//! let mut graph = AdjacencyList::of_size(n);
+//! graph.add_edges(edges);
+//! ```
+//!
+//! 重みがすべて 1 であれば、頂点の組のリストから直接生成することもできる。
+//!
+//! ```rust
+//! # use procon_lib::pcl::structure::graph::AdjacencyList;
+//! // use crate::pcl::structure::graph::AdjacencyList;
+//! let edges = [(0, 2), (0, 3), (1, 4), (1, 5), (1, 6), (2, 7), (2, 8)];
+//! let graph = AdjacencyList::<i32>::from_edges(9, &edges);
+//! ```
+
+pub mod algo;
+pub mod csr;
+pub mod flow;
+pub mod grid;
+pub mod hld;
+pub mod lca;
+pub mod tree;
+pub mod two_sat;
+
+pub use self::algo::{
+    bfs01, bfs_dist, bfs_visit, count_spanning_trees, dfs_visit, dijkstra, dijkstra_with_prev,
+    find_directed_cycle, is_valid_bfs_order, kruskal, min_arborescence, reconstruct_path,
+    strongly_connected_components, topological_sort, two_coloring, TopoCycleError,
+};
+pub use self::csr::CsrGraph;
+pub use self::flow::{bipartite_matching_flow, MaxFlow};
+pub use self::grid::grid_graph;
+pub use self::hld::Hld;
+pub use self::lca::LcaTree;
+pub use self::tree::{all_pairs_distances, dsu_on_tree, euler_tour, rooting, virtual_tree, Rooted};
+pub use self::two_sat::TwoSat;
+
+use crate::pcl::compat::num::{One, Zero};
+use crate::pcl::traits::math::graph::{Edge, Graph, ProvideAdjacencies, ReadonlyGraph, Undirected};
+use crate::{member_name_of, type_name_of};
+use std::cmp::PartialOrd;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter;
+use std::ops::Add;
+
+/// 辺をリストとして所持するタイプのグラフ。
+pub struct EdgeList<C> {
+    size: usize,
+    edges: Vec<Edge<C>>,
+}
+
+impl<C> fmt::Debug for EdgeList<C>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name_of!(EdgeList<C>))
+            .field(member_name_of!(self.size), &self.size)
+            .field(member_name_of!(self.edges), &self.edges)
+            .finish()
+    }
+}
+
+impl<C: Clone> Clone for EdgeList<C> {
+    fn clone(&self) -> Self {
+        Self {
+            size: self.size,
+            edges: self.edges.clone(),
+        }
+    }
+}
+
+impl<C> ReadonlyGraph for EdgeList<C> {
+    type Cost = C;
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<C> Graph for EdgeList<C> {
+    fn of_size(n: usize) -> Self {
+        Self {
+            size: n,
+            edges: vec![],
+        }
+    }
+
+    fn add_edge<E: Into<Edge<C>>>(&mut self, edge: E) {
+        self.edges.push(edge.into());
+    }
+
+    fn remove_edge_exact<E: Into<Edge<C>>>(&mut self, edge: E)
+    where
+        C: Eq,
+    {
+        let edge = edge.into();
+        self.edges.retain(|e| e != &edge);
+    }
+
+    fn remove_edge(&mut self, from: usize, to: usize) {
+        self.edges.retain(|e| e.from != from || e.to != to);
+    }
+}
+
+impl<C> EdgeList<C> {
+    /// すべての辺のリストを得る。
+    pub fn edges(&self) -> &[Edge<C>] {
+        &self.edges
+    }
+}
+
+/// 隣接リスト形式のグラフ。
+pub struct AdjacencyList<C> {
+    size: usize,
+    adjacencies: Vec<Vec<Edge<C>>>,
+}
+
+impl<C> fmt::Debug for AdjacencyList<C>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name_of!(AdjacencyList<C>))
+            .field(member_name_of!(self.size), &self.size)
+            .field(member_name_of!(self.adjacencies), &self.adjacencies)
+            .finish()
+    }
+}
+
+impl<C: Clone> Clone for AdjacencyList<C> {
+    fn clone(&self) -> Self {
+        Self {
+            size: self.size,
+            adjacencies: self.adjacencies.clone(),
+        }
+    }
+}
+
+impl<C> ProvideAdjacencies for AdjacencyList<C> {
+    fn get_adjacencies(&self, idx: usize) -> Option<&[Edge<C>]> {
+        self.adjacencies.get(idx).map(|x| &**x)
+    }
+}
+
+impl<C> ReadonlyGraph for AdjacencyList<C> {
+    type Cost = C;
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<C> Graph for AdjacencyList<C> {
+    fn of_size(n: usize) -> Self {
+        AdjacencyList {
+            size: n,
+            adjacencies: iter::from_fn(|| Some(Vec::new())).take(n).collect(),
+        }
+    }
+
+    fn add_edge<E: Into<Edge<C>>>(&mut self, edge: E) {
+        let edge = edge.into();
+        self.adjacencies[edge.from].push(edge);
+    }
+
+    fn remove_edge(&mut self, from: usize, to: usize) {
+        self.adjacencies[from].retain(|e| e.to != to);
+    }
+
+    fn remove_edge_exact<E: Into<Edge<C>>>(&mut self, edge: E)
+    where
+        C: Eq,
+    {
+        let edge = edge.into();
+        self.adjacencies[edge.from].retain(|e| e != &edge);
+    }
+}
+
+impl<C> AdjacencyList<C> {
+    /// すべての辺を、始点の番号の順に列挙する。
+    pub fn edges(&self) -> impl Iterator<Item = &Edge<C>> {
+        self.adjacencies.iter().flat_map(|adj| adj.iter())
+    }
+
+    /// 頂点 `v` から出る辺の本数を返す。
+    pub fn degree(&self, v: usize) -> usize {
+        self.adjacencies[v].len()
+    }
+}
+
+impl<C: One> AdjacencyList<C> {
+    /// 頂点数 `n` と辺のリストからグラフを生成する。各辺の重みは 1 になる。
+    pub fn from_edges(n: usize, edges: &[(usize, usize)]) -> AdjacencyList<C> {
+        let mut graph = AdjacencyList::of_size(n);
+        graph.add_edges(edges.iter().map(|&(from, to)| Edge::one(from, to)));
+        graph
+    }
+}
+
+impl<C> From<EdgeList<C>> for AdjacencyList<C> {
+    fn from(edge_list: EdgeList<C>) -> AdjacencyList<C> {
+        let mut graph = AdjacencyList::of_size(edge_list.size());
+        graph.add_edges(edge_list.edges);
+        graph
+    }
+}
+
+/// 隣接リスト形式の無向グラフ。
+pub struct UndirectedAdjacencyList<C>(AdjacencyList<C>);
+
+impl<C: fmt::Debug> fmt::Debug for UndirectedAdjacencyList<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name_of!(UndirectedAdjacencyList<C>))
+            .field(member_name_of!(AdjacencyList<C>::size), &self.0.size)
+            .field(
+                member_name_of!(AdjacencyList<C>::adjacencies),
+                &self.0.adjacencies,
+            )
+            .finish()
+    }
+}
+
+impl<C: Clone> Clone for UndirectedAdjacencyList<C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<C> ReadonlyGraph for UndirectedAdjacencyList<C> {
+    type Cost = C;
+
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+}
+
+impl<C> Graph for UndirectedAdjacencyList<C>
+where
+    C: Clone,
+{
+    fn of_size(n: usize) -> Self {
+        Self(AdjacencyList::of_size(n))
+    }
+
+    fn add_edge<E: Into<Edge<C>>>(&mut self, edge: E) {
+        let edge = edge.into();
+        self.0.add_edge(edge.clone());
+        self.0.add_edge(edge.reversed());
+    }
+
+    fn remove_edge(&mut self, from: usize, to: usize) {
+        self.0.remove_edge(from, to);
+        self.0.remove_edge(to, from);
+    }
+
+    fn remove_edge_exact<E: Into<Edge<C>>>(&mut self, edge: E)
+    where
+        C: Eq,
+    {
+        let edge = edge.into();
+        self.0.remove_edge_exact(edge.clone());
+        self.0.remove_edge_exact(edge.reversed());
+    }
+}
+
+impl<C> UndirectedAdjacencyList<C> {
+    /// すべての辺を、始点の番号の順に列挙する。
+    ///
+    /// 一本の無向辺は向きを変えて二回 (自己ループも二回) 現れることに注意。
+    pub fn edges(&self) -> impl Iterator<Item = &Edge<C>> {
+        self.0.edges()
+    }
+
+    /// 頂点 `v` の次数を返す。自己ループは 2 と数える。
+    pub fn degree(&self, v: usize) -> usize {
+        self.0.degree(v)
+    }
+}
+
+impl<C: One + Clone> UndirectedAdjacencyList<C> {
+    /// 頂点数 `n` と辺のリストから無向グラフを生成する。各辺の重みは 1 になる。
+    pub fn from_edges(n: usize, edges: &[(usize, usize)]) -> UndirectedAdjacencyList<C> {
+        let mut graph = UndirectedAdjacencyList::of_size(n);
+        graph.add_edges(edges.iter().map(|&(from, to)| Edge::one(from, to)));
+        graph
+    }
+}
+
+impl<C> Undirected for UndirectedAdjacencyList<C> {}
+
+impl<C> ProvideAdjacencies for UndirectedAdjacencyList<C> {
+    fn get_adjacencies(&self, idx: usize) -> Option<&[Edge<C>]> {
+        self.0.get_adjacencies(idx)
+    }
+}
+
+/// 隣接行列形式のグラフ。
+///
+/// 頂点 `from` から `to` への辺のコストを `n × n` の表で持つので、同じ頂点間には高々一本の辺しか持
+/// てない。`add_edge()` は既存の辺を上書きする。Floyd–Warshall 法などで使う密なグラフ向け。
+///
+/// ```rust
+/// # use procon_lib::pcl::structure::graph::AdjacencyMatrix;
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::AdjacencyMatrix;
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = AdjacencyMatrix::of_size(3);
+/// graph.add_edge((0, 1, 5));
+/// graph.add_edge((0, 1, 3));
+/// graph.add_edge_min((1, 2, 4));
+/// graph.add_edge_min((1, 2, 7));
+/// assert_eq!(graph.cost(0, 1), Some(&3));
+/// assert_eq!(graph.cost(1, 2), Some(&4));
+/// assert_eq!(graph.cost(1, 0), None);
+/// ```
+pub struct AdjacencyMatrix<C> {
+    size: usize,
+    matrix: Vec<Vec<Option<C>>>,
+}
+
+impl<C> fmt::Debug for AdjacencyMatrix<C>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name_of!(AdjacencyMatrix<C>))
+            .field(member_name_of!(self.size), &self.size)
+            .field(member_name_of!(self.matrix), &self.matrix)
+            .finish()
+    }
+}
+
+impl<C: Clone> Clone for AdjacencyMatrix<C> {
+    fn clone(&self) -> Self {
+        Self {
+            size: self.size,
+            matrix: self.matrix.clone(),
+        }
+    }
+}
+
+impl<C> ReadonlyGraph for AdjacencyMatrix<C> {
+    type Cost = C;
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<C> Graph for AdjacencyMatrix<C> {
+    fn of_size(n: usize) -> Self {
+        AdjacencyMatrix {
+            size: n,
+            matrix: (0..n).map(|_| (0..n).map(|_| None).collect()).collect(),
+        }
+    }
+
+    fn add_edge<E: Into<Edge<C>>>(&mut self, edge: E) {
+        let edge = edge.into();
+        self.matrix[edge.from][edge.to] = Some(edge.cost);
+    }
+
+    fn remove_edge(&mut self, from: usize, to: usize) {
+        self.matrix[from][to] = None;
+    }
+
+    fn remove_edge_exact<E: Into<Edge<C>>>(&mut self, edge: E)
+    where
+        C: Eq,
+    {
+        let edge = edge.into();
+        let cell = &mut self.matrix[edge.from][edge.to];
+        if cell.as_ref() == Some(&edge.cost) {
+            *cell = None;
+        }
+    }
+}
+
+impl<C> AdjacencyMatrix<C> {
+    /// 頂点 `from` から `to` への辺のコストを返す。辺がなければ `None` を返す。
+    pub fn cost(&self, from: usize, to: usize) -> Option<&C> {
+        self.matrix[from][to].as_ref()
+    }
+}
+
+impl<C: PartialOrd> AdjacencyMatrix<C> {
+    /// 辺を追加する。すでに同じ頂点間に辺があれば、コストの小さい方を残す。
+    pub fn add_edge_min<E: Into<Edge<C>>>(&mut self, edge: E) {
+        let edge = edge.into();
+        let cell = &mut self.matrix[edge.from][edge.to];
+        let replace = match cell {
+            Some(cost) => edge.cost < *cost,
+            None => true,
+        };
+        if replace {
+            *cell = Some(edge.cost);
+        }
+    }
+}
+
+impl<C> From<EdgeList<C>> for AdjacencyMatrix<C> {
+    /// 辺リストから変換する。同じ頂点間に複数の辺があれば、後の辺で上書きされる。
+    fn from(edge_list: EdgeList<C>) -> AdjacencyMatrix<C> {
+        let mut graph = AdjacencyMatrix::of_size(edge_list.size());
+        graph.add_edges(edge_list.edges);
+        graph
+    }
+}
+
+/// ツリー。ここでは無向グラフで連結かつサイクルを持たないものをいう。
+///
+/// ツリーは構造を保つかどうかをリアルタイムに判断することが難しいため、直接生成することはできない。
+/// まずは [`UndirectedAdjacencyList`] でグラフを作り、それが木構造を持っていることを確かめた上で初
+/// めて変換することができる。
+///
+/// ```rust
+/// # use procon_lib::pcl::structure::graph::{UndirectedAdjacencyList, Tree};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{UndirectedAdjacencyList, Tree};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// use std::convert::TryFrom;
+/// let mut graph = UndirectedAdjacencyList::<i32>::of_size(4);
+/// graph.add_edges(vec![
+///     (0, 1),
+///     (1, 2),
+///     (1, 3),
+/// ]);
+/// let tree = Tree::try_from(graph); // ここで条件を満たしているか確認する。
+/// assert!(tree.is_ok());
+/// ```
+pub struct Tree<C>(UndirectedAdjacencyList<C>);
+
+impl<C: fmt::Debug> fmt::Debug for Tree<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // self.0.0.size みたいなのはエラーになるようなので
+        let inner = &self.0;
+        f.debug_struct(type_name_of!(Tree<C>))
+            .field(member_name_of!(AdjacencyList<C>::size), &inner.0.size)
+            .field(
+                member_name_of!(AdjacencyList<C>::adjacencies),
+                &inner.0.adjacencies,
+            )
+            .finish()
+    }
+}
+
+impl<C> ReadonlyGraph for Tree<C> {
+    type Cost = C;
+
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+}
+
+impl<C> Undirected for Tree<C> {}
+
+impl<C> ProvideAdjacencies for Tree<C> {
+    fn get_adjacencies(&self, idx: usize) -> Option<&[Edge<C>]> {
+        self.0.get_adjacencies(idx)
+    }
+}
+
+impl<C> Tree<C> {
+    /// 隣接リスト形式の無向グラフから生成する。
+    pub fn try_from_graph(graph: UndirectedAdjacencyList<C>) -> Result<Self, TreeTryFromError> {
+        is_tree(&graph).map(|_| Self(graph))
+    }
+
+    /// 隣接リスト形式の無向グラフからチェックせずに生成する。
+    ///
+    /// # Safety
+    ///
+    /// 与えられたグラフは連結で閉路がない。`is_tree(&graph)` が `Ok(_)` である。
+    pub unsafe fn from_graph_unchecked(graph: UndirectedAdjacencyList<C>) -> Self {
+        Self(graph)
+    }
+}
+
+impl Tree<i64> {
+    /// 各頂点の親を並べた配列から生成する。根の親は `None` とする。
+    ///
+    /// 根がちょうど一つでない場合や、親をたどると閉路になる場合は木にならないので `Err` を返す。各
+    /// 辺の重みは 1 になる。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn from_parent_array(parents: &[Option<usize>]) -> Result<Tree<i64>, TreeTryFromError> {
+        let n = parents.len();
+        let mut graph = UndirectedAdjacencyList::of_size(n);
+        for (v, &p) in parents.iter().enumerate() {
+            if let Some(p) = p {
+                assert!(p < n, "parent out of range: {} is the parent of {}", p, v);
+                graph.add_edge((p, v));
+            }
+        }
+
+        Tree::try_from_graph(graph)
+    }
+}
+
+impl<C> Tree<C>
+where
+    C: Zero + for<'c> Add<&'c C, Output = C> + PartialOrd,
+{
+    /// 直径を求める
+    pub fn diameter(&self) -> C {
+        fn max_depth<C>(graph: &Tree<C>, idx: usize, stepped: &mut HashSet<usize>) -> (C, usize)
+        where
+            C: Zero + for<'c> Add<&'c C, Output = C> + PartialOrd,
+        {
+            let mut res = None;
+            assert!(stepped.insert(idx));
+            for edge in graph
+                .get_adjacencies(idx)
+                .expect("vertex index out of bounds")
+            {
+                if stepped.contains(&edge.to) {
+                    continue;
+                }
+                let (further_cost, furthest) = max_depth(graph, edge.to, stepped);
+                let total_cost = further_cost + &edge.cost;
+                if Some(&total_cost) > res.as_ref().map(|(cost, _)| cost) {
+                    res = Some((total_cost, furthest));
+                }
+            }
+
+            res.unwrap_or((C::zero(), idx))
+        }
+
+        let (_, furthest) = max_depth(self, 0, &mut HashSet::new());
+        let (d, _) = max_depth(self, furthest, &mut HashSet::new());
+        d
+    }
+}
+
+#[cfg(feature = "rust-138")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// ツリーに変換できなかった理由を示す。
+pub enum TreeTryFromError {
+    /// 連結でない。
+    NotConnected,
+
+    /// 閉路を持つ。
+    HasCycle,
+
+    /// 連結でもなければ閉路も持つ。
+    Both,
+}
+
+#[cfg(feature = "rust-138")]
+impl<C> TryFrom<UndirectedAdjacencyList<C>> for Tree<C> {
+    type Error = TreeTryFromError;
+
+    fn try_from(graph: UndirectedAdjacencyList<C>) -> Result<Self, Self::Error> {
+        Tree::try_from_graph(graph)
+    }
+}
+
+/// 木を `root` を根として見たときの、各頂点の親を並べた配列を求める。根の親は `None` となる。
+///
+/// # 計算量
+///
+/// O(n)
+pub fn to_parent_array<C>(tree: &Tree<C>, root: usize) -> Vec<Option<usize>> {
+    let mut parents = vec![None; tree.size()];
+    let mut visited = vec![false; tree.size()];
+    let mut stack = vec![root];
+    visited[root] = true;
+    while let Some(v) = stack.pop() {
+        for edge in tree.get_adjacencies(v).expect("vertex index out of bounds") {
+            if visited[edge.to] {
+                continue;
+            }
+            visited[edge.to] = true;
+            parents[edge.to] = Some(v);
+            stack.push(edge.to);
+        }
+    }
+
+    parents
+}
+
+/// 与えられた無向グラフが木かどうかを確認する。
+///
+/// すなわち次の2つの条件を満たすことを確かめる。
+/// 1. グラフが連結
+/// 2. サイクルがない
+pub fn is_tree<G: Undirected + ProvideAdjacencies>(graph: &G) -> Result<(), TreeTryFromError> {
+    match (is_connected(graph), has_cycle(graph)) {
+        (true, false) => Ok(()),
+        (false, false) => Err(TreeTryFromError::NotConnected),
+        (true, true) => Err(TreeTryFromError::HasCycle),
+        (false, true) => Err(TreeTryFromError::Both),
+    }
+}
+
+/// 与えられた無向グラフがサイクルを持つかどうかを確認する。
+///
+/// 多重辺 (同じ頂点の間の二本以上の辺) や自己ループもサイクルとみなす。
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn has_cycle<G: Undirected + ProvideAdjacencies>(graph: &G) -> bool {
+    // 深さ優先探索で全域森を作る。親から来た辺以外で訪問済みの頂点に出会えばサイクルがある。無向グ
+    // ラフでは各辺が両方の頂点の隣接リストに現れるので、親への辺は一本だけ読み飛ばす。二本目以降は
+    // 多重辺なのでサイクルになる。
+    let n = graph.size();
+    let mut visited = vec![false; n];
+    for s in 0..n {
+        if visited[s] {
+            continue;
+        }
+
+        visited[s] = true;
+        let mut stack = vec![(s, None)];
+        while let Some((v, parent)) = stack.pop() {
+            let mut parent_skipped = false;
+            for edge in graph
+                .get_adjacencies(v)
+                .expect("vertex index out of bounds")
+            {
+                if !parent_skipped && Some(edge.to) == parent {
+                    parent_skipped = true;
+                    continue;
+                }
+                if visited[edge.to] {
+                    return true;
+                }
+                visited[edge.to] = true;
+                stack.push((edge.to, Some(v)));
+            }
+        }
+    }
+
+    false
+}
+
+/// 与えられた無向グラフが連結かどうかを確認する。頂点数が 0 や 1 のグラフは連結とみなす。
+///
+/// # 計算量
+///
+/// O(V + E)
+pub fn is_connected<G: Undirected + ProvideAdjacencies>(graph: &G) -> bool {
+    let n = graph.size();
+    if n <= 1 {
+        return true;
+    }
+
+    // 頂点 0 から深さ優先探索をしてすべての頂点を訪れられるかどうかを調べれば良い。
+    let mut visited = vec![false; n];
+    let mut stack = vec![0];
+    let mut count = 1;
+    visited[0] = true;
+    while let Some(v) = stack.pop() {
+        for edge in graph
+            .get_adjacencies(v)
+            .expect("vertex index out of bounds")
+        {
+            if visited[edge.to] {
+                continue;
+            }
+            visited[edge.to] = true;
+            count += 1;
+            stack.push(edge.to);
+        }
+    }
+
+    count == n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_connected() {
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(3);
+        graph.add_edge((0, 2));
+        assert!(!is_connected(&graph));
+        graph.add_edge((0, 1));
+        assert!(is_connected(&graph));
+
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(9);
+        let edges = [(0, 2), (0, 3), (1, 4), (1, 5), (1, 6), (2, 7), (2, 8)];
+        graph.add_edges(edges.iter().copied());
+        assert!(!is_connected(&graph));
+    }
+
+    #[test]
+    fn test_is_connected_trivial_and_deep() {
+        assert!(is_connected(&UndirectedAdjacencyList::<i32>::of_size(0)));
+        assert!(is_connected(&UndirectedAdjacencyList::<i32>::of_size(1)));
+        assert!(!is_connected(&UndirectedAdjacencyList::<i32>::of_size(2)));
+
+        // 深いパスでもスタックを溢れさせない。
+        let n = 200_000;
+        let edges: Vec<_> = (1..n).map(|v| (v - 1, v)).collect();
+        assert!(is_connected(&UndirectedAdjacencyList::<i32>::from_edges(
+            n, &edges
+        )));
+        assert!(!is_connected(&UndirectedAdjacencyList::<i32>::from_edges(
+            n,
+            &edges[1..]
+        )));
+    }
+
+    #[test]
+    fn test_has_cycle() {
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(4);
+        graph.add_edge((0, 2));
+        graph.add_edge((0, 3));
+        graph.add_edge((1, 2));
+        assert!(!has_cycle(&graph));
+        graph.add_edge((0, 1));
+        assert!(has_cycle(&graph));
+
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(9);
+        let edges = [(0, 2), (0, 3), (1, 4), (1, 5), (1, 6), (2, 7), (2, 8)];
+        graph.add_edges(edges.iter().copied());
+        assert!(!has_cycle(&graph));
+    }
+
+    #[test]
+    fn test_has_cycle_multigraph() {
+        // 二頂点の間の多重辺
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(2, &[(0, 1), (1, 0)]);
+        assert!(has_cycle(&graph));
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(3, &[(2, 1), (0, 1), (1, 2)]);
+        assert!(has_cycle(&graph));
+
+        // 自己ループ (根でも根以外でも)
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(1, &[(0, 0)]);
+        assert!(has_cycle(&graph));
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(3, &[(0, 1), (1, 2), (2, 2)]);
+        assert!(has_cycle(&graph));
+
+        // 木や森、辺のないグラフにはサイクルがない。
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(2, &[(0, 1)]);
+        assert!(!has_cycle(&graph));
+        let edges = [(0, 1), (1, 2), (1, 3), (3, 4), (5, 6)];
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(8, &edges);
+        assert!(!has_cycle(&graph));
+        assert!(!has_cycle(&UndirectedAdjacencyList::<i32>::of_size(0)));
+
+        // 深いパスでもスタックを溢れさせない。
+        let n = 200_000;
+        let edges: Vec<_> = (1..n).map(|v| (v - 1, v)).collect();
+        let mut graph = UndirectedAdjacencyList::<i32>::from_edges(n, &edges);
+        assert!(!has_cycle(&graph));
+        graph.add_edge((n - 1, 0));
+        assert!(has_cycle(&graph));
+    }
+
+    #[test]
+    fn test_from_edges() {
+        let edges = [(0, 2), (0, 3), (1, 4), (1, 5), (1, 6), (2, 7), (2, 8)];
+
+        let graph = AdjacencyList::<i32>::from_edges(9, &edges);
+        let mut expected = AdjacencyList::<i32>::of_size(9);
+        expected.add_edges(edges.iter().map(|&(from, to)| Edge::new(from, to, 1)));
+        assert_eq!(graph.size(), 9);
+        for v in 0..9 {
+            assert_eq!(graph.get_adjacencies(v), expected.get_adjacencies(v));
+        }
+        assert_eq!(graph.get_adjacencies(2).unwrap().len(), 2);
+
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(9, &edges);
+        assert_eq!(graph.get_adjacencies(2).unwrap().len(), 3);
+        assert!(!is_connected(&graph));
+        assert!(!has_cycle(&graph));
+    }
+
+    #[test]
+    fn test_edges_and_degree() {
+        let mut graph = AdjacencyList::<i32>::of_size(4);
+        graph.add_edges(vec![(0, 1, 5), (2, 0, 1), (0, 3, 2), (3, 3, 4)]);
+        assert_eq!(graph.edges().count(), 4);
+        assert_eq!(
+            graph.edges().map(|e| (e.from, e.to)).collect::<Vec<_>>(),
+            vec![(0, 1), (0, 3), (2, 0), (3, 3)]
+        );
+        assert_eq!(graph.edges().map(|e| e.cost).sum::<i32>(), 12);
+        assert_eq!(
+            (0..4).map(|v| graph.degree(v)).collect::<Vec<_>>(),
+            vec![2, 0, 1, 1]
+        );
+
+        // 無向グラフでは各辺が二回ずつ現れ、次数の和は辺の本数の 2 倍になる。
+        let edges = [(0, 2), (0, 3), (1, 4), (2, 4), (4, 4)];
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(5, &edges);
+        assert_eq!(graph.edges().count(), 2 * edges.len());
+        assert_eq!(
+            (0..5).map(|v| graph.degree(v)).collect::<Vec<_>>(),
+            vec![2, 1, 2, 1, 4]
+        );
+        assert_eq!(
+            (0..5).map(|v| graph.degree(v)).sum::<usize>(),
+            graph.edges().count()
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let edges = [(0, 2), (0, 3), (1, 4), (1, 5), (1, 6), (2, 7), (2, 8)];
+        let graph = UndirectedAdjacencyList::<i32>::from_edges(9, &edges);
+        let mut neighbors: Vec<_> = graph.neighbors(2).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![0, 7, 8]);
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![4, 5, 6]);
+
+        let graph = AdjacencyList::<i32>::from_edges(9, &edges);
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![7, 8]);
+        assert_eq!(graph.neighbors(8).count(), 0);
+    }
+
+    #[test]
+    fn test_adjacency_matrix() {
+        let mut edge_list = EdgeList::of_size(4);
+        edge_list.add_edges(vec![(0, 1, 3), (1, 2, -2), (2, 0, 7), (3, 3, 1)]);
+        let mut graph = AdjacencyMatrix::from(edge_list.clone());
+        assert_eq!(graph.size(), 4);
+        for from in 0..4 {
+            for to in 0..4 {
+                let expected = edge_list
+                    .edges()
+                    .iter()
+                    .find(|e| e.from == from && e.to == to)
+                    .map(|e| &e.cost);
+                assert_eq!(graph.cost(from, to), expected);
+            }
+        }
+
+        // 上書きと小さい方の採用
+        graph.add_edge((0, 1, 10));
+        assert_eq!(graph.cost(0, 1), Some(&10));
+        graph.add_edge_min((0, 1, 20));
+        assert_eq!(graph.cost(0, 1), Some(&10));
+        graph.add_edge_min((0, 1, 4));
+        assert_eq!(graph.cost(0, 1), Some(&4));
+        graph.add_edge_min((1, 0, 9));
+        assert_eq!(graph.cost(1, 0), Some(&9));
+
+        // 削除
+        graph.remove_edge_exact((2, 0, 8));
+        assert_eq!(graph.cost(2, 0), Some(&7));
+        graph.remove_edge_exact((2, 0, 7));
+        assert_eq!(graph.cost(2, 0), None);
+        graph.remove_edge(1, 2);
+        assert_eq!(graph.cost(1, 2), None);
+        assert_eq!(graph.cost(3, 3), Some(&1));
+    }
+
+    #[test]
+    fn test_tree() {
+        #[cfg(not(feature = "rust-142"))]
+        use crate::matches;
+
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(9);
+        let edges = [(0, 2), (0, 3), (1, 4), (1, 5), (1, 6), (2, 7), (2, 8)];
+        graph.add_edges(edges.iter().copied());
+        assert!(matches!(
+            Tree::try_from(graph.clone()),
+            Err(TreeTryFromError::NotConnected)
+        ));
+
+        graph.add_edge((0, 1));
+        let tree = match Tree::try_from(graph.clone()) {
+            Ok(t) => t,
+            Err(_) => panic!(),
+        };
+        assert!(is_tree(&tree).is_ok());
+
+        graph.add_edge((1, 2));
+        assert!(matches!(
+            Tree::try_from(graph),
+            Err(TreeTryFromError::HasCycle)
+        ));
+    }
+
+    #[test]
+    fn test_tree_diameter() {
+        let mut graph = UndirectedAdjacencyList::<i32>::of_size(10);
+        graph.add_edges(vec![
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (3, 4),
+            (3, 5),
+            (2, 6),
+            (6, 7),
+            (7, 8),
+            (7, 9),
+        ]);
+        let tree = Tree::try_from(graph).expect("this is indeed tree");
+        assert_eq!(tree.diameter(), 7);
+    }
+
+    #[test]
+    fn test_tree_parent_array() {
+        let mut graph = UndirectedAdjacencyList::<i64>::of_size(6);
+        graph.add_edges(vec![(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)]);
+        let tree = Tree::try_from(graph).expect("this is indeed tree");
+
+        let parents = to_parent_array(&tree, 0);
+        assert_eq!(
+            parents,
+            vec![None, Some(0), Some(0), Some(1), Some(1), Some(2)]
+        );
+        let rebuilt = Tree::from_parent_array(&parents).expect("this is indeed tree");
+        assert_eq!(to_parent_array(&rebuilt, 0), parents);
+        assert_eq!(rebuilt.diameter(), tree.diameter());
+
+        // 別の頂点を根にしても同じ木に戻る。
+        let parents = to_parent_array(&tree, 4);
+        assert_eq!(
+            parents,
+            vec![Some(1), Some(4), Some(0), Some(1), None, Some(2)]
+        );
+        let rebuilt = Tree::from_parent_array(&parents).expect("this is indeed tree");
+        assert_eq!(to_parent_array(&rebuilt, 0), to_parent_array(&tree, 0));
+    }
+
+    #[test]
+    fn test_tree_from_invalid_parent_array() {
+        #[cfg(not(feature = "rust-142"))]
+        use crate::matches;
+
+        // 根が二つ
+        assert!(matches!(
+            Tree::from_parent_array(&[None, Some(0), None, Some(2)]),
+            Err(TreeTryFromError::NotConnected)
+        ));
+        // 根がなく閉路になっている
+        assert!(Tree::from_parent_array(&[Some(2), Some(0), Some(1)]).is_err());
+    }
+}
@@ -0,0 +1,611 @@
+//! 木に関するアルゴリズムを定義する。
+
+use super::{LcaTree, Tree};
+use crate::pcl::compat::num::Zero;
+use crate::pcl::traits::math::graph::{ProvideAdjacencies, ReadonlyGraph};
+use std::ops::Add;
+
+/// 木のすべての頂点の組について、その間のパスの長さを求める。
+///
+/// 各頂点から深さ優先探索を行うだけの素朴な実装である。頂点数が小さく O(V^2) で十分な場合に、LCA
+/// などを用意するまでもないときに使う。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{all_pairs_distances, Tree, UndirectedAdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{all_pairs_distances, Tree, UndirectedAdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// let mut graph = UndirectedAdjacencyList::of_size(3);
+/// graph.add_edges(vec![(0, 1, 2), (1, 2, 5)]);
+/// let tree = Tree::try_from_graph(graph).unwrap();
+/// let dist = all_pairs_distances(&tree);
+/// assert_eq!(dist[0], vec![0, 2, 7]);
+/// assert_eq!(dist[2][1], 5);
+/// ```
+///
+/// # 計算量
+///
+/// O(V^2)
+pub fn all_pairs_distances<C>(tree: &Tree<C>) -> Vec<Vec<C>>
+where
+    C: Add<Output = C> + Copy + Zero,
+{
+    let n = tree.size();
+    let mut res = Vec::with_capacity(n);
+    for source in 0..n {
+        let mut dist = vec![C::zero(); n];
+        let mut visited = vec![false; n];
+        let mut stack = vec![source];
+        visited[source] = true;
+        while let Some(v) = stack.pop() {
+            for edge in tree.get_adjacencies(v).expect("vertex index out of bounds") {
+                if visited[edge.to] {
+                    continue;
+                }
+                visited[edge.to] = true;
+                dist[edge.to] = dist[v] + edge.cost;
+                stack.push(edge.to);
+            }
+        }
+        res.push(dist);
+    }
+
+    res
+}
+
+/// 根付き木の部分木に関するクエリを、いわゆる DSU on tree (small-to-large) でまとめて処理する。
+///
+/// 呼び出し側は部分木の頂点の集合を表すデータ構造 (色ごとの個数など) を用意しておき、三つのコール
+/// バックを通してそれを操作する。
+///
+/// - `add(v)`: 頂点 `v` をデータ構造に加える。
+/// - `remove(v)`: 頂点 `v` をデータ構造から取り除く。`add` した頂点に対してのみ呼ばれる。
+/// - `query(v)`: このときデータ構造にはちょうど `v` の部分木の頂点が入っている。各頂点についてちょ
+///   うど一度呼ばれる。
+///
+/// 最初はデータ構造が空であるものとし、戻ったときも空になっている。コールバックは同じデータ構造を
+/// 共有することになるので、`RefCell` などを通して操作するとよい。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{dsu_on_tree, Tree, UndirectedAdjacencyList};
+/// # use procon_lib::pcl::traits::math::graph::Graph;
+/// // use crate::pcl::structure::graph::{dsu_on_tree, Tree, UndirectedAdjacencyList};
+/// // use crate::pcl::traits::math::graph::Graph;
+/// use std::cell::RefCell;
+///
+/// let mut graph = UndirectedAdjacencyList::<i32>::of_size(4);
+/// graph.add_edges(vec![(0, 1), (0, 2), (2, 3)]);
+/// let tree = Tree::try_from_graph(graph).unwrap();
+/// let weight = [1, 10, 100, 1000];
+///
+/// // 部分木の重みの和
+/// let sum = RefCell::new(0);
+/// let mut res = vec![0; 4];
+/// dsu_on_tree(
+///     &tree,
+///     0,
+///     |v| *sum.borrow_mut() += weight[v],
+///     |v| *sum.borrow_mut() -= weight[v],
+///     |v| res[v] = *sum.borrow(),
+/// );
+/// assert_eq!(res, vec![1111, 10, 1100, 1000]);
+/// ```
+///
+/// # 計算量
+///
+/// `add` と `remove` の呼び出しがそれぞれ O(V log V) 回
+pub fn dsu_on_tree<C, F, G, H>(tree: &Tree<C>, root: usize, mut add: F, mut remove: G, mut query: H)
+where
+    F: FnMut(usize),
+    G: FnMut(usize),
+    H: FnMut(usize),
+{
+    let n = tree.size();
+    assert!(
+        root < n,
+        "root out of range: root is {} but n is {}",
+        root,
+        n
+    );
+
+    // 行きがけ順に並べる。各部分木は `order` の連続した区間になる。
+    let mut parent = vec![None; n];
+    let mut order = Vec::with_capacity(n);
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        order.push(v);
+        for edge in tree.get_adjacencies(v).expect("vertex index out of bounds") {
+            if Some(edge.to) != parent[v] {
+                parent[edge.to] = Some(v);
+                stack.push(edge.to);
+            }
+        }
+    }
+
+    let mut tin = vec![0; n];
+    for (i, &v) in order.iter().enumerate() {
+        tin[v] = i;
+    }
+    let mut size = vec![1; n];
+    for &v in order.iter().rev() {
+        if let Some(p) = parent[v] {
+            size[p] += size[v];
+        }
+    }
+
+    // 子を軽い子、重い子の順に並べておく。
+    let mut children = vec![Vec::new(); n];
+    for &v in &order {
+        if let Some(p) = parent[v] {
+            children[p].push(v);
+        }
+    }
+    for list in &mut children {
+        if let Some(heavy) = (0..list.len()).max_by_key(|&i| size[list[i]]) {
+            let last = list.len() - 1;
+            list.swap(heavy, last);
+        }
+    }
+
+    // 再帰の代わりに (頂点, 部分木を残すか, 次に処理する子の番号) をスタックに積む。
+    let mut stack = vec![(root, false, 0)];
+    while let Some(&(v, keep, next)) = stack.last() {
+        if let Some(&c) = children[v].get(next) {
+            stack.last_mut().unwrap().2 += 1;
+            // 重い子 (最後の子) の部分木だけは取り除かずに残しておく。
+            stack.push((c, next + 1 == children[v].len(), 0));
+            continue;
+        }
+
+        stack.pop();
+        add(v);
+        let light = children[v].len().saturating_sub(1);
+        for &c in &children[v][..light] {
+            for &u in &order[tin[c]..tin[c] + size[c]] {
+                add(u);
+            }
+        }
+        query(v);
+        if !keep {
+            for &u in &order[tin[v]..tin[v] + size[v]] {
+                remove(u);
+            }
+        }
+    }
+}
+
+/// 根付き木としての情報。`rooting` の戻り値。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rooted {
+    /// 各頂点の親。根の親は `None` 。
+    pub parent: Vec<Option<usize>>,
+    /// 各頂点の深さ (根からの辺の本数) 。
+    pub depth: Vec<usize>,
+    /// 頂点を深さ優先探索の行きがけ順に並べたもの。親は必ず子より前に現れるので、逆順にたどれば
+    /// 葉から根への木 DP ができる。
+    pub order: Vec<usize>,
+    /// 各頂点を根とする部分木の頂点数。
+    pub subtree_size: Vec<usize>,
+}
+
+/// `tree` を `root` を根とする根付き木とみなして、親、深さ、行きがけ順、部分木の大きさを求める。
+///
+/// 再帰を使わないので、深い木でもスタックを溢れさせない。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{rooting, Tree};
+/// // use crate::pcl::structure::graph::{rooting, Tree};
+/// let tree = Tree::from_parent_array(&[None, Some(0), Some(1), Some(0)]).unwrap();
+/// let rooted = rooting(&tree, 1);
+/// assert_eq!(rooted.parent, vec![Some(1), None, Some(1), Some(0)]);
+/// assert_eq!(rooted.depth, vec![1, 0, 1, 2]);
+/// assert_eq!(rooted.subtree_size, vec![2, 4, 1, 1]);
+/// ```
+///
+/// # 計算量
+///
+/// O(n)
+pub fn rooting<C>(tree: &Tree<C>, root: usize) -> Rooted {
+    let n = tree.size();
+    assert!(
+        root < n,
+        "root out of range: root is {} but n is {}",
+        root,
+        n
+    );
+
+    let mut parent = vec![None; n];
+    let mut depth = vec![0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        order.push(v);
+        for edge in tree.get_adjacencies(v).expect("vertex index out of bounds") {
+            if Some(edge.to) != parent[v] {
+                parent[edge.to] = Some(v);
+                depth[edge.to] = depth[v] + 1;
+                stack.push(edge.to);
+            }
+        }
+    }
+
+    let mut subtree_size = vec![1; n];
+    for &v in order.iter().rev() {
+        if let Some(p) = parent[v] {
+            subtree_size[p] += subtree_size[v];
+        }
+    }
+
+    Rooted {
+        parent,
+        depth,
+        order,
+        subtree_size,
+    }
+}
+
+/// `root` を根とした深さ優先探索の行きがけ順で、各頂点の入る時刻 `tin` と出る時刻 `tout` を求め
+/// る。
+///
+/// 頂点 `v` の部分木は行きがけ順の半開区間 `tin[v]..tout[v]` にちょうど対応する。頂点を `tin` の位
+/// 置に置いたフェニック木やセグメント木で部分木に関するクエリを扱える。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{euler_tour, Tree};
+/// // use crate::pcl::structure::graph::{euler_tour, Tree};
+/// let tree = Tree::from_parent_array(&[None, Some(0), Some(1), Some(0)]).unwrap();
+/// let (tin, tout) = euler_tour(&tree, 0);
+/// assert_eq!((tin[0], tout[0]), (0, 4));
+/// assert_eq!(tout[1] - tin[1], 2);
+/// assert_eq!(tout[3] - tin[3], 1);
+/// ```
+///
+/// # 計算量
+///
+/// O(n)
+pub fn euler_tour<C>(tree: &Tree<C>, root: usize) -> (Vec<usize>, Vec<usize>) {
+    let rooted = rooting(tree, root);
+    let n = tree.size();
+    let mut tin = vec![0; n];
+    let mut tout = vec![0; n];
+    for (i, &v) in rooted.order.iter().enumerate() {
+        tin[v] = i;
+        tout[v] = i + rooted.subtree_size[v];
+    }
+
+    (tin, tout)
+}
+
+/// 頂点の部分集合 `vertices` とそれらの LCA だけからなる圧縮された木 (auxiliary tree, virtual
+/// tree) を求める。
+///
+/// 戻り値は (頂点のリスト, 辺のリスト) で、頂点は `lca` の行きがけ順に並び、先頭が圧縮された木の根
+/// になる。各辺は (親, 子) の組で、元の木でのパスを一本の辺に縮めたものである。`vertices` に重複が
+/// あってもよい。
+///
+/// ```
+/// # use procon_lib::pcl::structure::graph::{virtual_tree, LcaTree, Tree};
+/// // use crate::pcl::structure::graph::{virtual_tree, LcaTree, Tree};
+/// //     0
+/// //    / \
+/// //   1   2
+/// //  / \
+/// // 3   4
+/// let tree = Tree::from_parent_array(&[None, Some(0), Some(0), Some(1), Some(1)]).unwrap();
+/// let lca = LcaTree::new(&tree, 0);
+/// let (vs, mut edges) = virtual_tree(&tree, &lca, &[3, 4]);
+/// assert_eq!(vs.len(), 3);
+/// edges.sort();
+/// assert_eq!(edges, vec![(1, 3), (1, 4)]);
+/// ```
+///
+/// # 計算量
+///
+/// 頂点の個数を k として O(k log k + k log n)
+pub fn virtual_tree<C>(
+    tree: &Tree<C>,
+    lca: &LcaTree,
+    vertices: &[usize],
+) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let n = tree.size();
+    for &v in vertices {
+        assert!(v < n, "vertex out of range: v is {} but n is {}", v, n);
+    }
+
+    // 行きがけ順で隣り合う頂点の LCA を加えると、LCA について閉じた集合になる。
+    let mut vs = vertices.to_vec();
+    vs.sort_by_key(|&v| lca.tin(v));
+    vs.dedup();
+    for i in 1..vs.len() {
+        let w = lca.lca(vs[i - 1], vs[i]);
+        vs.push(w);
+    }
+    vs.sort_by_key(|&v| lca.tin(v));
+    vs.dedup();
+
+    // 行きがけ順に見ながら、根から現在の頂点までのパス上の頂点をスタックで持つ。
+    let mut edges = Vec::with_capacity(vs.len().saturating_sub(1));
+    let mut stack: Vec<usize> = Vec::new();
+    for &v in &vs {
+        while let Some(&top) = stack.last() {
+            if lca.lca(top, v) == top {
+                break;
+            }
+            stack.pop();
+        }
+        if let Some(&top) = stack.last() {
+            edges.push((top, v));
+        }
+        stack.push(v);
+    }
+
+    (vs, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::structure::graph::UndirectedAdjacencyList;
+    use crate::pcl::test_util::XorShift;
+    use crate::pcl::traits::math::graph::Graph;
+
+    #[test]
+    fn test_all_pairs_distances() {
+        //        0
+        //     3/   \1
+        //     1     2
+        //   2/ \4    \6
+        //   3   4     5
+        let mut graph = UndirectedAdjacencyList::of_size(6);
+        graph.add_edges(vec![(0, 1, 3), (0, 2, 1), (1, 3, 2), (1, 4, 4), (2, 5, 6)]);
+        let tree = Tree::try_from_graph(graph).expect("this is indeed tree");
+        let dist = all_pairs_distances(&tree);
+
+        assert_eq!(dist.len(), 6);
+        assert_eq!(dist[0], vec![0, 3, 1, 5, 7, 7]);
+        assert_eq!(dist[3][4], 6);
+        assert_eq!(dist[3][5], 12);
+        assert_eq!(dist[4][2], 8);
+        for (u, row) in dist.iter().enumerate() {
+            assert_eq!(row[u], 0);
+            for (v, &d) in row.iter().enumerate() {
+                assert_eq!(d, dist[v][u]);
+            }
+        }
+
+        let max = dist.iter().flat_map(|row| row.iter()).max().cloned();
+        assert_eq!(max, Some(tree.diameter()));
+    }
+
+    #[test]
+    fn test_dsu_on_tree() {
+        use std::cell::RefCell;
+        use std::collections::HashSet;
+
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for n in 1..40 {
+            let parents: Vec<_> = (0..n)
+                .map(|v| {
+                    if v == 0 {
+                        None
+                    } else {
+                        Some((rng.next_u64() % v as u64) as usize)
+                    }
+                })
+                .collect();
+            let tree = Tree::from_parent_array(&parents).unwrap();
+            let color: Vec<_> = (0..n).map(|_| rng.next_u64() % 5).collect();
+            let root = (rng.next_u64() % n as u64) as usize;
+
+            // 色ごとの個数と、現在の種類数
+            let counts = RefCell::new((vec![0; 5], 0));
+            let mut res = vec![None; n];
+            dsu_on_tree(
+                &tree,
+                root,
+                |v| {
+                    let (ref mut cnt, ref mut distinct) = *counts.borrow_mut();
+                    cnt[color[v] as usize] += 1;
+                    if cnt[color[v] as usize] == 1 {
+                        *distinct += 1;
+                    }
+                },
+                |v| {
+                    let (ref mut cnt, ref mut distinct) = *counts.borrow_mut();
+                    cnt[color[v] as usize] -= 1;
+                    if cnt[color[v] as usize] == 0 {
+                        *distinct -= 1;
+                    }
+                },
+                |v| {
+                    assert_eq!(res[v], None);
+                    res[v] = Some(counts.borrow().1);
+                },
+            );
+            assert_eq!(counts.into_inner(), (vec![0; 5], 0));
+
+            // 素朴に部分木を列挙して数える。
+            let mut parent = vec![None; n];
+            let mut stack = vec![root];
+            while let Some(u) = stack.pop() {
+                for e in tree.get_adjacencies(u).unwrap() {
+                    if Some(e.to) != parent[u] {
+                        parent[e.to] = Some(u);
+                        stack.push(e.to);
+                    }
+                }
+            }
+            for (v, &r) in res.iter().enumerate() {
+                let mut colors = HashSet::new();
+                let mut stack = vec![v];
+                while let Some(u) = stack.pop() {
+                    colors.insert(color[u]);
+                    for e in tree.get_adjacencies(u).unwrap() {
+                        if Some(e.to) != parent[u] {
+                            stack.push(e.to);
+                        }
+                    }
+                }
+                assert_eq!(r, Some(colors.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_virtual_tree() {
+        //           0
+        //        /  |  \
+        //       1   2   3
+        //      / \      |
+        //     4   5     6
+        //    /         / \
+        //   7         8   9
+        let parents = [
+            None,
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(1),
+            Some(1),
+            Some(3),
+            Some(4),
+            Some(6),
+            Some(6),
+        ];
+        let tree = Tree::from_parent_array(&parents).unwrap();
+        let lca = LcaTree::new(&tree, 0);
+        let build = |vertices: &[usize]| {
+            let (mut vs, mut edges) = virtual_tree(&tree, &lca, vertices);
+            // 先頭が根で、頂点は行きがけ順に並ぶ。
+            assert!(vs.windows(2).all(|w| lca.tin(w[0]) < lca.tin(w[1])));
+            assert_eq!(edges.len(), vs.len().saturating_sub(1));
+            if let Some(&root) = vs.first() {
+                assert!(vs.iter().all(|&v| lca.lca(root, v) == root));
+            }
+            vs.sort();
+            edges.sort();
+            (vs, edges)
+        };
+
+        assert_eq!(
+            build(&[7, 5, 8]),
+            (vec![0, 1, 5, 7, 8], vec![(0, 1), (0, 8), (1, 5), (1, 7)])
+        );
+        assert_eq!(build(&[9, 8, 8]), (vec![6, 8, 9], vec![(6, 8), (6, 9)]));
+        // 祖先と子孫が含まれる場合
+        assert_eq!(
+            build(&[7, 1, 9, 3]),
+            (vec![0, 1, 3, 7, 9], vec![(0, 1), (0, 3), (1, 7), (3, 9)])
+        );
+        assert_eq!(build(&[4]), (vec![4], vec![]));
+        assert_eq!(build(&[]), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_rooting() {
+        //        0
+        //      / | \
+        //     1  2  3
+        //    / \     \
+        //   4   5     6
+        //       |
+        //       7
+        let parents = [
+            None,
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(1),
+            Some(1),
+            Some(3),
+            Some(5),
+        ];
+        let tree = Tree::from_parent_array(&parents).unwrap();
+        let rooted = rooting(&tree, 0);
+        assert_eq!(rooted.parent, parents.to_vec());
+        assert_eq!(rooted.depth, vec![0, 1, 1, 1, 2, 2, 2, 3]);
+        assert_eq!(rooted.subtree_size, vec![8, 4, 1, 2, 1, 2, 1, 1]);
+        assert_eq!(rooted.order.len(), 8);
+        assert_eq!(rooted.order[0], 0);
+        let mut pos = [0; 8];
+        for (i, &v) in rooted.order.iter().enumerate() {
+            pos[v] = i;
+        }
+        for v in 1..8 {
+            let p = rooted.parent[v].unwrap();
+            // 親は子より前にあり、部分木は行きがけ順で連続する。
+            assert!(
+                pos[p] < pos[v]
+                    && pos[v] + rooted.subtree_size[v] <= pos[p] + rooted.subtree_size[p]
+            );
+        }
+
+        // 別の頂点を根にする。
+        let rooted = rooting(&tree, 5);
+        assert_eq!(
+            rooted.parent,
+            vec![
+                Some(1),
+                Some(5),
+                Some(0),
+                Some(0),
+                Some(1),
+                None,
+                Some(3),
+                Some(5)
+            ]
+        );
+        assert_eq!(rooted.depth, vec![2, 1, 3, 3, 2, 0, 4, 1]);
+        assert_eq!(rooted.subtree_size, vec![4, 6, 1, 2, 1, 8, 1, 1]);
+
+        // 深いパスでもスタックを溢れさせない。
+        let n: usize = 200_000;
+        let parents: Vec<_> = (0..n).map(|v| v.checked_sub(1)).collect();
+        let rooted = rooting(&Tree::from_parent_array(&parents).unwrap(), 0);
+        assert_eq!(rooted.depth[n - 1], n - 1);
+        assert_eq!(rooted.subtree_size[1], n - 1);
+    }
+
+    #[test]
+    fn test_euler_tour() {
+        let mut rng = XorShift::new(2_463_534_242);
+
+        for n in 1..50 {
+            let parents: Vec<_> = (0..n)
+                .map(|v| {
+                    if v == 0 {
+                        None
+                    } else {
+                        Some((rng.next_u64() % v as u64) as usize)
+                    }
+                })
+                .collect();
+            let tree = Tree::from_parent_array(&parents).unwrap();
+            let root = (rng.next_u64() % n as u64) as usize;
+            let (tin, tout) = euler_tour(&tree, root);
+            let parent = rooting(&tree, root).parent;
+
+            let mut seen = vec![false; n];
+            for v in 0..n {
+                assert!(!seen[tin[v]]);
+                seen[tin[v]] = true;
+            }
+            assert_eq!((tin[root], tout[root]), (0, n));
+
+            // u が v の祖先であることと、u の区間が v の区間を含むことは同値。
+            for v in 0..n {
+                let mut ancestors = vec![false; n];
+                let mut x = Some(v);
+                while let Some(u) = x {
+                    ancestors[u] = true;
+                    x = parent[u];
+                }
+                for u in 0..n {
+                    let nested = tin[u] <= tin[v] && tout[v] <= tout[u];
+                    assert_eq!(nested, ancestors[u]);
+                }
+            }
+        }
+    }
+}
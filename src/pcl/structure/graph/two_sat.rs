@@ -0,0 +1,158 @@
+//! 2-SAT を解く `TwoSat` を定義する。
+//!
+//! 「`x_i == f` または `x_j == g`」という形の節をいくつか与え、すべてを満たす真偽値の割り当てを求め
+//! る。各リテラルを頂点とする含意グラフを作り、その強連結成分分解から割り当てを決める。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::graph::TwoSat;
+//! // use crate::pcl::structure::graph::TwoSat;
+//! let mut sat = TwoSat::new(2);
+//! // x_0 または x_1
+//! sat.add_clause(0, true, 1, true);
+//! // !x_0 または !x_1
+//! sat.add_clause(0, false, 1, false);
+//! // x_0 (同じリテラル同士の節で、一つのリテラルを強制できる)
+//! sat.add_clause(0, true, 0, true);
+//! assert_eq!(sat.solve(), Some(vec![true, false]));
+//!
+//! sat.add_clause(1, true, 1, true);
+//! assert_eq!(sat.solve(), None);
+//! ```
+
+use super::algo::strongly_connected_components;
+use super::AdjacencyList;
+use crate::pcl::traits::math::graph::Graph;
+
+/// 2-SAT のソルバ。
+///
+/// 変数 `x_i` が `f` であるというリテラルを、含意グラフの頂点 `2 * i + f as usize` で表す。
+#[derive(Debug, Clone)]
+pub struct TwoSat {
+    n: usize,
+    graph: AdjacencyList<i32>,
+}
+
+impl TwoSat {
+    /// 変数 `n` 個で、節のない問題を生成する。
+    pub fn new(n: usize) -> TwoSat {
+        TwoSat {
+            n,
+            graph: AdjacencyList::of_size(2 * n),
+        }
+    }
+
+    /// 節「`x_i == f` または `x_j == g`」を追加する。
+    pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        let n = self.n;
+        assert!(i < n, "index out of range: i is {} but n is {}", i, n);
+        assert!(j < n, "index out of range: j is {} but n is {}", j, n);
+
+        // 片方が成り立たなければ、もう片方が成り立たなければならない。
+        self.graph.add_edge((literal(i, !f), literal(j, g)));
+        self.graph.add_edge((literal(j, !g), literal(i, f)));
+    }
+
+    /// すべての節を満たす割り当てを一つ求める。存在しなければ `None` を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(n + m) (m は節の数)
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let comp = strongly_connected_components(&self.graph);
+        (0..self.n)
+            .map(|i| {
+                let (t, f) = (comp[literal(i, true)], comp[literal(i, false)]);
+                // 強連結成分の番号は、トポロジカル順序で後ろにあるものほど小さい。x_i から !x_i へ
+                // 到達できるなら x_i は偽でなければならないので、後ろにある方を真とする。
+                if t == f {
+                    None
+                } else {
+                    Some(t < f)
+                }
+            })
+            .collect()
+    }
+}
+
+fn literal(i: usize, f: bool) -> usize {
+    2 * i + f as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+
+    /// 節 `(i, f, j, g)` をすべて満たすかどうか。
+    fn satisfies(clauses: &[(usize, bool, usize, bool)], assignment: &[bool]) -> bool {
+        clauses
+            .iter()
+            .all(|&(i, f, j, g)| assignment[i] == f || assignment[j] == g)
+    }
+
+    #[test]
+    fn two_sat_satisfiable() {
+        let clauses = [
+            (0, true, 1, false),
+            (1, true, 2, true),
+            (2, false, 0, false),
+            (3, true, 3, true),
+            (3, false, 1, true),
+        ];
+        let mut sat = TwoSat::new(4);
+        for &(i, f, j, g) in &clauses {
+            sat.add_clause(i, f, j, g);
+        }
+        let assignment = sat.solve().unwrap();
+        assert_eq!(assignment.len(), 4);
+        assert!(satisfies(&clauses, &assignment));
+
+        assert_eq!(TwoSat::new(0).solve(), Some(vec![]));
+    }
+
+    #[test]
+    fn two_sat_unsatisfiable() {
+        // x_0 == x_1, x_1 == x_2, x_2 != x_0 は同時に満たせない。
+        let mut sat = TwoSat::new(3);
+        for &(i, j, same) in &[(0, 1, true), (1, 2, true), (2, 0, false)] {
+            sat.add_clause(i, true, j, !same);
+            sat.add_clause(i, false, j, same);
+        }
+        assert_eq!(sat.solve(), None);
+    }
+
+    #[test]
+    fn two_sat_matches_brute_force() {
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+
+        for _ in 0..300 {
+            let n = (rng.next_u64() % 6 + 1) as usize;
+            let clauses: Vec<_> = (0..rng.next_u64() % 12)
+                .map(|_| {
+                    (
+                        (rng.next_u64() % n as u64) as usize,
+                        rng.next_u64() % 2 == 1,
+                        (rng.next_u64() % n as u64) as usize,
+                        rng.next_u64() % 2 == 1,
+                    )
+                })
+                .collect();
+
+            let mut sat = TwoSat::new(n);
+            for &(i, f, j, g) in &clauses {
+                sat.add_clause(i, f, j, g);
+            }
+
+            let exists = (0..1 << n).any(|mask: usize| {
+                let assignment: Vec<_> = (0..n).map(|i| mask >> i & 1 == 1).collect();
+                satisfies(&clauses, &assignment)
+            });
+            match sat.solve() {
+                Some(assignment) => assert!(satisfies(&clauses, &assignment)),
+                None => assert!(!exists),
+            }
+        }
+    }
+}
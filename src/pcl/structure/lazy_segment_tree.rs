@@ -0,0 +1,476 @@
+//! 遅延評価セグメント木 `LazySegmentTree` を定義する。
+//!
+//! 遅延評価セグメント木は、配列に対して次の操作が高速に行えるデータ構造である。
+//!
+//! - ある区間の各要素に作用素を作用させる (`apply_range`) ― O(log n)
+//! - ある区間の値にそのモノイドの演算を適用した結果を返す (`query`) ― O(log n)
+//!
+//! 値はモノイド `M` 、作用素はモノイド作用 `F: Action<M>` で与える。区間加算・区間最小値のように
+//! `Min` や `Max` を扱う場合は作用素に `Affine` が使える。よく使う組み合わせとして、区間代入・区間加
+//! 算・区間和を扱う `RangeAssignRangeSum` も用意している。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::structure::lazy_segment_tree::RangeAssignRangeSum;
+//! // use crate::pcl::structure::lazy_segment_tree::RangeAssignRangeSum;
+//! let mut st = RangeAssignRangeSum::from_array(vec![1, 2, 3, 4, 5]);
+//! assert_eq!(st.sum(..), 15);
+//! st.assign(1..4, 10);
+//! assert_eq!(st.sum(..), 36);
+//! st.add(0..2, 1);
+//! assert_eq!(st.sum(0..2), 13);
+//! ```
+
+use crate::pcl::traits::math::{Action, Monoid};
+use crate::pcl::utils::range;
+use std::fmt;
+use std::ops::RangeBounds;
+
+/// 遅延評価セグメント木。
+pub struct LazySegmentTree<M, F> {
+    data: Vec<M>,
+    lazy: Vec<F>,
+    log: usize,
+    lenexp2: usize,
+    len: usize,
+}
+
+impl<M: fmt::Debug, F: fmt::Debug> fmt::Debug for LazySegmentTree<M, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazySegmentTree")
+            .field("data", &self.data)
+            .field("lazy", &self.lazy)
+            .finish()
+    }
+}
+
+impl<M, F> LazySegmentTree<M, F>
+where
+    M: Monoid + Copy,
+    F: Action<M> + Copy,
+{
+    /// 初期値を持つ配列から遅延評価セグメント木を生成する。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn from_array<A: AsRef<[M]>>(arr: A) -> LazySegmentTree<M, F> {
+        let arr = arr.as_ref();
+        let len = arr.len();
+        let mut log = 0;
+        while (1 << log) < len {
+            log += 1;
+        }
+        let lenexp2 = 1 << log;
+
+        let mut data = vec![M::id(); lenexp2 * 2];
+        data[lenexp2..(lenexp2 + len)].copy_from_slice(arr);
+        let lazy = vec![F::id(); lenexp2];
+
+        let mut st = LazySegmentTree {
+            data,
+            lazy,
+            log,
+            lenexp2,
+            len,
+        };
+        for k in (1..lenexp2).rev() {
+            st.pull(k);
+        }
+
+        st
+    }
+
+    /// ある区間 `range` の各要素に作用素 `f` を作用させる。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn apply_range<R: RangeBounds<usize>>(&mut self, range: R, f: F) {
+        let start = range::range_start(&range, 0);
+        let end = range::range_end(&range, self.len);
+        if end <= start {
+            return;
+        }
+
+        let l = start + self.lenexp2;
+        let r = end + self.lenexp2;
+        self.push_boundaries(l, r);
+
+        {
+            let mut l = l;
+            let mut r = r;
+            while l < r {
+                if l & 1 != 0 {
+                    self.apply_node(l, f);
+                    l += 1;
+                }
+                if r & 1 != 0 {
+                    r -= 1;
+                    self.apply_node(r, f);
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+
+        for i in 1..=self.log {
+            if ((l >> i) << i) != l {
+                self.pull(l >> i);
+            }
+            if ((r >> i) << i) != r {
+                self.pull((r - 1) >> i);
+            }
+        }
+    }
+
+    /// ある区間 `range` の各要素に順に演算を適用して、結果を返す。
+    ///
+    /// 遅延させていた作用を伝播させるため `&mut self` を取る。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn query<R: RangeBounds<usize>>(&mut self, range: R) -> M {
+        let start = range::range_start(&range, 0);
+        let end = range::range_end(&range, self.len);
+        if end <= start {
+            return M::id();
+        }
+
+        let mut l = start + self.lenexp2;
+        let mut r = end + self.lenexp2;
+        self.push_boundaries(l, r);
+
+        let mut res1 = M::id();
+        let mut res2 = M::id();
+        while l < r {
+            if l & 1 != 0 {
+                res1 = M::op(res1, self.data[l]);
+                l += 1;
+            }
+            if r & 1 != 0 {
+                r -= 1;
+                res2 = M::op(self.data[r], res2);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+
+        M::op(res1, res2)
+    }
+
+    /// もとの配列の長さを取得する。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// もとの配列が空かどうかを返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 葉 `l` から葉 `r - 1` までの区間に関わるノードについて、上から順に遅延させていた作用を伝播
+    /// させる。
+    fn push_boundaries(&mut self, l: usize, r: usize) {
+        for i in (1..=self.log).rev() {
+            if ((l >> i) << i) != l {
+                self.push(l >> i);
+            }
+            if ((r >> i) << i) != r {
+                self.push((r - 1) >> i);
+            }
+        }
+    }
+
+    /// 子の値からノード `k` の値を計算し直す。
+    fn pull(&mut self, k: usize) {
+        self.data[k] = M::op(self.data[2 * k], self.data[2 * k + 1]);
+    }
+
+    /// ノード `k` に作用素 `f` を作用させ、子へ伝播させる分を記録する。
+    ///
+    /// 配列の外側 (長さを 2 冪に揃えるための余白) だけを表すノードには作用させない。単位元に作用さ
+    /// せるとあふれたりするため。
+    fn apply_node(&mut self, k: usize, f: F) {
+        // ノード k が表す区間の左端の葉。k の深さは k のビット長 - 1 。
+        let depth = (0usize.leading_zeros() - k.leading_zeros()) as usize - 1;
+        if (k << (self.log - depth)) - self.lenexp2 >= self.len {
+            return;
+        }
+
+        self.data[k] = F::apply(f, self.data[k]);
+        if k < self.lenexp2 {
+            self.lazy[k] = F::op(self.lazy[k], f);
+        }
+    }
+
+    /// ノード `k` に遅延させていた作用を子へ伝播させる。
+    fn push(&mut self, k: usize) {
+        let f = self.lazy[k];
+        self.apply_node(2 * k, f);
+        self.apply_node(2 * k + 1, f);
+        self.lazy[k] = F::id();
+    }
+}
+
+/// 区間の和と区間の長さの組。
+///
+/// 区間代入や区間加算のように、作用の結果が区間の長さに依存する場合に値のモノイドとして使う。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SumLen {
+    pub sum: i64,
+    pub len: i64,
+}
+
+impl SumLen {
+    /// 長さ 1 の区間 (一つの要素) を表す値を生成する。
+    pub fn single(value: i64) -> SumLen {
+        SumLen { sum: value, len: 1 }
+    }
+}
+
+impl Monoid for SumLen {
+    fn op(x: Self, y: Self) -> Self {
+        SumLen {
+            sum: x.sum + y.sum,
+            len: x.len + y.len,
+        }
+    }
+
+    fn id() -> Self {
+        SumLen { sum: 0, len: 0 }
+    }
+}
+
+/// 区間代入と区間加算を合わせた作用素。
+///
+/// 各要素を `assign` で上書きし (`None` なら何もしない) 、その後 `add` を加える。後から代入が合成さ
+/// れると、それまでに溜まっていた代入や加算はすべて上書きされる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssignAdd {
+    pub assign: Option<i64>,
+    pub add: i64,
+}
+
+impl Monoid for AssignAdd {
+    fn op(f: Self, g: Self) -> Self {
+        if g.assign.is_some() {
+            g
+        } else {
+            AssignAdd {
+                assign: f.assign,
+                add: f.add + g.add,
+            }
+        }
+    }
+
+    fn id() -> Self {
+        AssignAdd {
+            assign: None,
+            add: 0,
+        }
+    }
+}
+
+impl Action<SumLen> for AssignAdd {
+    fn apply(f: Self, x: SumLen) -> SumLen {
+        let base = match f.assign {
+            Some(value) => value * x.len,
+            None => x.sum,
+        };
+
+        SumLen {
+            sum: base + f.add * x.len,
+            len: x.len,
+        }
+    }
+}
+
+/// 区間代入・区間加算・区間和を扱う遅延評価セグメント木。
+#[derive(Debug)]
+pub struct RangeAssignRangeSum {
+    tree: LazySegmentTree<SumLen, AssignAdd>,
+}
+
+impl RangeAssignRangeSum {
+    /// 初期値を持つ配列から生成する。
+    ///
+    /// # 計算量
+    ///
+    /// O(n)
+    pub fn from_array<A: AsRef<[i64]>>(arr: A) -> RangeAssignRangeSum {
+        let values: Vec<_> = arr.as_ref().iter().map(|&x| SumLen::single(x)).collect();
+        RangeAssignRangeSum {
+            tree: LazySegmentTree::from_array(values),
+        }
+    }
+
+    /// ある区間 `range` の各要素を `value` に書き換える。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn assign<R: RangeBounds<usize>>(&mut self, range: R, value: i64) {
+        let f = AssignAdd {
+            assign: Some(value),
+            add: 0,
+        };
+        self.tree.apply_range(range, f);
+    }
+
+    /// ある区間 `range` の各要素に `value` を加える。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, value: i64) {
+        let f = AssignAdd {
+            assign: None,
+            add: value,
+        };
+        self.tree.apply_range(range, f);
+    }
+
+    /// ある区間 `range` の和を求める。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn sum<R: RangeBounds<usize>>(&mut self, range: R) -> i64 {
+        self.tree.query(range).sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::test_util::XorShift;
+    use crate::pcl::traits::math::monoid::Min;
+    use crate::pcl::traits::math::Affine;
+
+    #[test]
+    fn range_assign_range_sum() {
+        let mut st = RangeAssignRangeSum::from_array(vec![1, 2, 3, 4, 5]);
+        assert_eq!(st.sum(..), 15);
+        st.add(0..5, 1);
+        assert_eq!(st.sum(..), 20);
+        // 溜まっていた加算は代入で上書きされる。
+        st.assign(0..5, 3);
+        assert_eq!(st.sum(..), 15);
+        assert_eq!(st.sum(2..3), 3);
+        st.add(1..3, 2);
+        st.assign(2..4, -1);
+        assert_eq!(st.sum(..), 3 + 5 - 1 - 1 + 3);
+    }
+
+    #[test]
+    fn range_assign_range_sum_naive() {
+        let n = 37;
+        let mut rng = XorShift::new(88_172_645_463_325_252);
+        let mut naive: Vec<i64> = (0..n as i64).collect();
+        let mut st = RangeAssignRangeSum::from_array(&naive);
+
+        for _ in 0..2000 {
+            let a = rng.next_u64() as usize % (n + 1);
+            let b = rng.next_u64() as usize % (n + 1);
+            let (l, r) = if a <= b { (a, b) } else { (b, a) };
+            let value = (rng.next_u64() % 100) as i64 - 50;
+            match rng.next_u64() % 3 {
+                0 => {
+                    st.assign(l..r, value);
+                    for x in &mut naive[l..r] {
+                        *x = value;
+                    }
+                }
+                1 => {
+                    st.add(l..r, value);
+                    for x in &mut naive[l..r] {
+                        *x += value;
+                    }
+                }
+                _ => {
+                    assert_eq!(st.sum(l..r), naive[l..r].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn range_add_range_min() {
+        let mut st = LazySegmentTree::from_array(vec![Min(5), Min(3), Min(8), Min(6)]);
+        assert_eq!(st.query(..).0, 3);
+        st.apply_range(0..2, Affine::add(4));
+        assert_eq!(st.query(..).0, 6);
+        assert_eq!(st.query(0..2).0, 7);
+        st.apply_range(1..3, Affine { mul: 2, add: -10 });
+        assert_eq!(st.query(1..3).0, 4);
+        assert_eq!(st.query(..).0, 4);
+        st.apply_range(3.., Affine::assign(-1));
+        assert_eq!(st.query(2..).0, -1);
+        assert_eq!(st.query(0..1).0, 9);
+        assert_eq!(st.query(2..2).0, Min::<i64>::id().0);
+    }
+
+    #[test]
+    fn range_assign_over_identity() {
+        // 単位元と同じ値の要素にも代入が行われる。
+        let mut st = LazySegmentTree::from_array(vec![Min::<i32>::id(); 3]);
+        st.apply_range(0..2, Affine::assign(1));
+        assert_eq!(st.query(0..2).0, 1);
+        assert_eq!(st.query(..).0, 1);
+        assert_eq!(st.query(2..).0, Min::<i32>::id().0);
+
+        // 長さが 2 冪でなくても余白には作用しないので、あふれない。
+        let mut st = LazySegmentTree::from_array(vec![Min(0i32); 5]);
+        st.apply_range(.., Affine::add(1));
+        st.apply_range(3.., Affine::add(2));
+        assert_eq!(st.query(..).0, 1);
+        assert_eq!(st.query(3..).0, 3);
+    }
+
+    #[test]
+    fn range_add_range_min_naive() {
+        let n = 29;
+        let mut rng = XorShift::new(2_463_534_242);
+        let mut naive: Vec<i64> = (0..n).map(|_| (rng.next_u64() % 1000) as i64).collect();
+        let values: Vec<_> = naive.iter().map(|&x| Min(x)).collect();
+        let mut st = LazySegmentTree::from_array(values);
+
+        for _ in 0..2000 {
+            let a = rng.next_u64() as usize % (n + 1);
+            let b = rng.next_u64() as usize % (n + 1);
+            let (l, r) = if a <= b { (a, b) } else { (b, a) };
+            if rng.next_u64() & 1 == 0 {
+                let value = (rng.next_u64() % 100) as i64 - 50;
+                st.apply_range(l..r, Affine::add(value));
+                for x in &mut naive[l..r] {
+                    *x += value;
+                }
+            } else {
+                let expected = naive[l..r]
+                    .iter()
+                    .min()
+                    .cloned()
+                    .unwrap_or(Min::<i64>::id().0);
+                assert_eq!(st.query(l..r).0, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_segment_tree_empty() {
+        let mut st = LazySegmentTree::<SumLen, AssignAdd>::from_array(vec![]);
+        assert!(st.is_empty());
+        assert_eq!(st.query(..), SumLen::id());
+    }
+}
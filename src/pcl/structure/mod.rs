@@ -1,9 +1,15 @@
 //! 各種データ構造を定義する。
 
+pub mod convex_hull_trick;
 pub mod disjoint_sets;
+pub mod fenwick;
 pub mod graph;
+pub mod lazy_segment_tree;
 pub mod segment_tree;
 
-pub use self::disjoint_sets::DisjointSets;
+pub use self::convex_hull_trick::ConvexHullTrick;
+pub use self::disjoint_sets::{DisjointSets, DisjointSetsMap, WeightedDisjointSets};
+pub use self::fenwick::{FenwickTree, FenwickTree2D, RangeFenwickTree};
 pub use self::graph::{AdjacencyList, EdgeList, Tree, UndirectedAdjacencyList};
-pub use self::segment_tree::SegmentTree;
+pub use self::lazy_segment_tree::{LazySegmentTree, RangeAssignRangeSum};
+pub use self::segment_tree::{RangeGcd, SegmentTree};
@@ -2,9 +2,10 @@
 //!
 //! セグメント木は、配列に対して次の操作が高速に行えるデータ構造である。
 //!
-//! - ある 1 要素の値の更新 (`update`) ― O(log n)
+//! - ある 1 要素の値の更新 (`update`) や、演算による値の追加 (`apply`) ― O(log n)
 //! - ある区間の値にそのモノイドの演算を適用した結果を返す (`query`) ― O(log n)
 //!     - 例えば `Additive` であれば区間の和、`Min` であれば Range Minimum Query 。
+//! - 区間の値に関する条件を満たす最長の区間を二分探索する (`max_right`, `min_left`) ― O(log n)
 //!
 //! # Examples
 //!
@@ -21,12 +22,16 @@
 //! assert_eq!(st.query(1..3).0, 2);
 //! ```
 
+use crate::pcl::traits::math::monoid::Gcd;
 use crate::pcl::traits::math::Monoid;
 use crate::pcl::utils::range;
 use std::fmt;
+use std::iter::FromIterator;
 use std::ops::RangeBounds;
 
 /// セグメント木。
+///
+/// 要素は `Copy` でなくてもよく、文字列を連結する `Concat` のようなモノイドも扱える。
 pub struct SegmentTree<T> {
     data: Vec<T>,
     lenexp2: usize,
@@ -36,14 +41,17 @@ pub struct SegmentTree<T> {
 impl<T: fmt::Debug> fmt::Debug for SegmentTree<T> {
     fn fmt<'a>(&self, f: &mut fmt::Formatter<'a>) -> fmt::Result {
         f.debug_struct("SegmentTree")
-            .field("data", &self.data)
+            .field(
+                "values",
+                &&self.data[self.lenexp2..(self.lenexp2 + self.len)],
+            )
             .finish()
     }
 }
 
 impl<T> SegmentTree<T>
 where
-    T: Monoid + Copy,
+    T: Monoid + Clone,
 {
     /// 初期値を持つ配列からセグメント木を生成する。
     pub fn from_array<A: AsRef<[T]>>(arr: A) -> SegmentTree<T> {
@@ -52,20 +60,46 @@ where
         let lenexp2 = calc_lenexp2(len);
         let data = {
             let mut v = vec![T::id(); lenexp2 * 2];
-            v[lenexp2..(lenexp2 + len)].copy_from_slice(arr);
+            v[lenexp2..(lenexp2 + len)].clone_from_slice(arr);
+            for k in (1..lenexp2).rev() {
+                v[k] = T::op(v[k * 2].clone(), v[k * 2 + 1].clone());
+            }
             v
         };
 
         SegmentTree { data, lenexp2, len }
     }
 
+    /// もとの配列の長さを取得する。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// もとの配列が空かどうかを返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     /// あるインデックス `idx` の値を `value` に更新する。
     ///
     /// # 計算量
     ///
     /// O(log n)
     pub fn update(&mut self, mut idx: usize, value: T) {
-        assert!(idx <= self.len);
+        assert!(
+            idx < self.len,
+            "index out of range: idx is {} but len is {}",
+            idx,
+            self.len
+        );
         idx += self.lenexp2;
         self.data[idx] = value;
 
@@ -74,10 +108,38 @@ where
             if idx == 0 {
                 break;
             }
-            self.data[idx] = T::op(self.data[idx * 2], self.data[idx * 2 + 1]);
+            self.data[idx] = T::op(self.data[idx * 2].clone(), self.data[idx * 2 + 1].clone());
         }
     }
 
+    /// あるインデックス `idx` の値を、現在の値と `value` に演算を適用した値に更新する。すなわち
+    /// `T::op(現在の値, value)` で置き換える。
+    ///
+    /// たとえばモノイド `Additive` であれば、その要素に `value` を加える。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn apply(&mut self, idx: usize, value: T) {
+        let current = self.get(idx);
+        self.update(idx, T::op(current, value));
+    }
+
+    /// あるインデックス `idx` の現在の値を取得する。
+    ///
+    /// # 計算量
+    ///
+    /// O(1)
+    pub fn get(&self, idx: usize) -> T {
+        assert!(
+            idx < self.len,
+            "index out of range: idx is {} but len is {}",
+            idx,
+            self.len
+        );
+        self.data[idx + self.lenexp2].clone()
+    }
+
     /// ある区間 `range` の各要素に順に演算を適用して、結果を返す。
     ///
     /// たとえばモノイド `Min` であれば、ある区間の最小値を返す。 (Range Minimum Query)
@@ -96,13 +158,13 @@ where
 
         while start < end {
             if start & 1 != 0 {
-                res1 = T::op(res1, self.data[start]);
+                res1 = T::op(res1, self.data[start].clone());
                 start += 1;
             }
 
             if end & 1 != 0 {
                 end -= 1;
-                res2 = T::op(self.data[end], res2);
+                res2 = T::op(self.data[end].clone(), res2);
             }
 
             start >>= 1;
@@ -111,12 +173,165 @@ where
 
         T::op(res1, res2)
     }
+
+    /// `pred(query(l..r))` が成り立つような最大の `r` を求める。
+    ///
+    /// `pred` は単調である、つまり区間を右に伸ばしていくとある所から先はずっと `false` になるとす
+    /// る。また `pred(T::id())` は `true` でなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn max_right<P: Fn(T) -> bool>(&self, l: usize, pred: P) -> usize {
+        assert!(
+            l <= self.len,
+            "index out of range: l is {} but len is {}",
+            l,
+            self.len
+        );
+        assert!(pred(T::id()), "pred(id) must be true");
+        if l == self.len {
+            return self.len;
+        }
+
+        let mut l = l + self.lenexp2;
+        let mut acc = T::id();
+        loop {
+            while l & 1 == 0 {
+                l >>= 1;
+            }
+
+            let next = T::op(acc.clone(), self.data[l].clone());
+            if !pred(next.clone()) {
+                // この部分木の中に境界がある。左の子から順に降りていく。
+                while l < self.lenexp2 {
+                    l *= 2;
+                    let next = T::op(acc.clone(), self.data[l].clone());
+                    if pred(next.clone()) {
+                        acc = next;
+                        l += 1;
+                    }
+                }
+                return l - self.lenexp2;
+            }
+
+            acc = next;
+            l += 1;
+            if l.is_power_of_two() {
+                break;
+            }
+        }
+
+        self.len
+    }
+
+    /// `pred(query(l..r))` が成り立つような最小の `l` を求める。
+    ///
+    /// `pred` は単調である、つまり区間を左に伸ばしていくとある所から先はずっと `false` になるとす
+    /// る。また `pred(T::id())` は `true` でなければならない。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn min_left<P: Fn(T) -> bool>(&self, r: usize, pred: P) -> usize {
+        assert!(
+            r <= self.len,
+            "index out of range: r is {} but len is {}",
+            r,
+            self.len
+        );
+        assert!(pred(T::id()), "pred(id) must be true");
+        if r == 0 {
+            return 0;
+        }
+
+        let mut r = r + self.lenexp2;
+        let mut acc = T::id();
+        loop {
+            r -= 1;
+            while r > 1 && r & 1 != 0 {
+                r >>= 1;
+            }
+
+            let next = T::op(self.data[r].clone(), acc.clone());
+            if !pred(next.clone()) {
+                // この部分木の中に境界がある。右の子から順に降りていく。
+                while r < self.lenexp2 {
+                    r = r * 2 + 1;
+                    let next = T::op(self.data[r].clone(), acc.clone());
+                    if pred(next.clone()) {
+                        acc = next;
+                        r -= 1;
+                    }
+                }
+                return r + 1 - self.lenexp2;
+            }
+
+            acc = next;
+            if r.is_power_of_two() {
+                break;
+            }
+        }
+
+        0
+    }
+}
+
+impl<T: Monoid + Clone> FromIterator<T> for SegmentTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> SegmentTree<T> {
+        let values: Vec<T> = iter.into_iter().collect();
+        SegmentTree::from_array(values)
+    }
+}
+
+/// 区間の最大公約数を求めるセグメント木。
+///
+/// `Gcd` で包まずに `i64` の値をそのまま扱えるメソッドを持つ。
+///
+/// ```
+/// # use procon_lib::pcl::structure::segment_tree::RangeGcd;
+/// // use crate::pcl::structure::segment_tree::RangeGcd;
+/// let mut st = RangeGcd::from_raw(&[12, 18, 27, 8]);
+/// assert_eq!(st.gcd(0..2), 6);
+/// assert_eq!(st.gcd(1..3), 9);
+/// st.update_raw(3, 36);
+/// assert_eq!(st.gcd(1..), 9);
+/// ```
+pub type RangeGcd = SegmentTree<Gcd<i64>>;
+
+impl SegmentTree<Gcd<i64>> {
+    /// `i64` の配列から生成する。
+    pub fn from_raw(values: &[i64]) -> RangeGcd {
+        values.iter().map(|&x| Gcd(x)).collect()
+    }
+
+    /// あるインデックス `idx` の値を `value` に更新する。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n)
+    pub fn update_raw(&mut self, idx: usize, value: i64) {
+        self.update(idx, Gcd(value));
+    }
+
+    /// 指定された範囲の最大公約数を返す。範囲が空なら 0 を返す。
+    ///
+    /// # 計算量
+    ///
+    /// O(log n log max)
+    pub fn gcd<R: RangeBounds<usize>>(&self, range: R) -> i64 {
+        self.query(range).0
+    }
 }
 
 /// 2 の冪乗であって最初に `len` 以上になるような値を求める。
 ///
-/// すなわち、 2^m >= `len` となるような最小の 2^m の値を求める。
+/// すなわち、 2^m >= `len` となるような最小の 2^m の値を求める。`len` が 0 なら 1 を返す。
 fn calc_lenexp2(mut len: usize) -> usize {
+    if len == 0 {
+        return 1;
+    }
+
     len -= 1;
     len |= len >> 1;
     len |= len >> 2;
@@ -131,7 +346,9 @@ fn calc_lenexp2(mut len: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pcl::traits::math::monoid::Min;
+    use crate::pcl::test_util::XorShift;
+    use crate::pcl::traits::math::group::Additive as A;
+    use crate::pcl::traits::math::monoid::{Concat, Max, Min, Monoid};
 
     #[test]
     fn segment_tree_1() {
@@ -145,6 +362,17 @@ mod tests {
         assert_eq!(st.query(1..3).0, 2);
     }
 
+    #[test]
+    fn segment_tree_from_array() {
+        // 更新せずに、生成した直後の区間の値を求める。
+        let st = SegmentTree::from_array(vec![Min(5i64), Min(3), Min(8), Min(1), Min(7)]);
+        assert_eq!(st.query(0..5).0, 1);
+        assert_eq!(st.query(0..3).0, 3);
+        assert_eq!(st.query(1..3).0, 3);
+        assert_eq!(st.query(2..3).0, 8);
+        assert_eq!(st.query(4..5).0, 7);
+    }
+
     #[test]
     fn segment_tree_2() {
         let mut st = SegmentTree::from_array(vec![Min((1i64 << 31) - 1); 1]);
@@ -152,4 +380,179 @@ mod tests {
         st.update(0, Min(5));
         assert_eq!(st.query(0..1).0, 5);
     }
+
+    #[test]
+    fn segment_tree_i128() {
+        let big = 1i128 << 100;
+        let mut st = SegmentTree::from_array(vec![Min(big), Min(-big), Min(big + 1)]);
+        assert_eq!(st.query(..).0, -big);
+        assert_eq!(st.query(2..).0, big + 1);
+        assert_eq!(st.query(1..1).0, Min::<i128>::id().0);
+        st.update(1, Min(big * 2));
+        assert_eq!(st.query(..).0, big);
+
+        let st = SegmentTree::from_array(vec![Max(0u128), Max(!0u128 >> 1)]);
+        assert_eq!(st.query(..).0, !0u128 >> 1);
+        assert_eq!(
+            SegmentTree::from_array(vec![A(big); 4]).query(1..).0,
+            big * 3
+        );
+    }
+
+    #[test]
+    fn segment_tree_max_right_min_left() {
+        let values = [3, 1, 4, 1, 5, 9, 2, 6, 5];
+        let n = values.len();
+        let st = SegmentTree::from_array(values.iter().map(|&x| A(x)).collect::<Vec<_>>());
+
+        // 和が k 以下である最長の区間
+        for k in 0..40 {
+            for l in 0..=n {
+                let expected = (l..=n)
+                    .rev()
+                    .find(|&r| values[l..r].iter().sum::<i64>() <= k)
+                    .unwrap();
+                assert_eq!(st.max_right(l, |x| x.0 <= k), expected);
+            }
+            for r in 0..=n {
+                let expected = (0..=r)
+                    .find(|&l| values[l..r].iter().sum::<i64>() <= k)
+                    .unwrap();
+                assert_eq!(st.min_left(r, |x| x.0 <= k), expected);
+            }
+        }
+
+        let st = SegmentTree::from_array(values.iter().map(|&x| Min(x)).collect::<Vec<_>>());
+        // 最小値が k 以上である最長の区間
+        assert_eq!(st.max_right(0, |x| x.0 >= 3), 1);
+        assert_eq!(st.max_right(4, |x| x.0 >= 5), 6);
+        assert_eq!(st.max_right(7, |x| x.0 >= 5), 9);
+        assert_eq!(st.min_left(9, |x| x.0 >= 5), 7);
+        assert_eq!(st.min_left(6, |x| x.0 >= 2), 4);
+        assert_eq!(st.min_left(3, |x| x.0 >= 0), 0);
+    }
+
+    #[test]
+    fn segment_tree_get() {
+        let mut st = SegmentTree::from_array(vec![A(0); 5]);
+        let mut expected = [0; 5];
+        for (i, &(idx, value)) in [(2, 5), (0, -1), (4, 3), (2, 7), (1, 2)].iter().enumerate() {
+            st.update(idx, A(value));
+            expected[idx] = value;
+            for (j, &x) in expected.iter().enumerate() {
+                assert_eq!(st.get(j).0, x, "after update #{}", i);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn segment_tree_get_out_of_range() {
+        SegmentTree::from_array([A(0); 3]).get(3);
+    }
+
+    #[test]
+    fn segment_tree_apply() {
+        let mut st = SegmentTree::from_array(vec![A(0i64); 6]);
+        let mut naive = [0i64; 6];
+        for i in 0..50 {
+            let idx = i * 7 % 6;
+            let value = i as i64 - 20;
+            st.apply(idx, A(value));
+            naive[idx] += value;
+            assert_eq!(st.get(idx).0, naive[idx]);
+            assert_eq!(st.query(..).0, naive.iter().sum::<i64>());
+            assert_eq!(st.query(1..4).0, naive[1..4].iter().sum::<i64>());
+        }
+
+        // update は上書きする。
+        st.update(2, A(100));
+        st.apply(2, A(1));
+        assert_eq!(st.get(2).0, 101);
+    }
+
+    #[test]
+    fn segment_tree_from_iter() {
+        let st: SegmentTree<_> = (1..=5).map(A).collect();
+        assert_eq!(st.len(), 5);
+        assert!(!st.is_empty());
+        assert_eq!(st.query(..).0, 15);
+        assert_eq!(st.query(1..4).0, 9);
+
+        let st: SegmentTree<Min<i32>> = (0..0).map(Min).collect();
+        assert_eq!(st.len(), 0);
+        assert!(st.is_empty());
+        assert_eq!(st.query(..).0, Min::<i32>::id().0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn segment_tree_update_out_of_range() {
+        // 長さ 3 なので内部の配列は長さ 4 に切り上げられているが、その余りに書き込んではいけない。
+        let mut st = SegmentTree::from_array([A(1), A(2), A(3)]);
+        st.update(3, A(4));
+    }
+
+    #[test]
+    fn segment_tree_debug() {
+        let st = SegmentTree::from_array([A(1), A(2), A(3)]);
+        assert_eq!(
+            format!("{:?}", st),
+            "SegmentTree { values: [Additive(1), Additive(2), Additive(3)] }"
+        );
+    }
+
+    #[test]
+    fn segment_tree_concat() {
+        let s = "segmenttree";
+        let mut st: SegmentTree<_> = s.chars().map(|c| Concat(c.to_string())).collect();
+        assert_eq!(st.query(..).0, s);
+        assert_eq!(st.query(3..7).0, "ment");
+        assert_eq!(st.query(5..5).0, "");
+        for l in 0..=s.len() {
+            for r in l..=s.len() {
+                assert_eq!(st.query(l..r).0, &s[l..r]);
+            }
+        }
+
+        st.update(0, Concat("S".to_string()));
+        st.apply(6, Concat("-".to_string()));
+        assert_eq!(st.query(..).0, "Segment-tree");
+        assert_eq!(st.get(6).0, "t-");
+
+        // 長さ 4 以下の接頭辞 / 接尾辞
+        assert_eq!(st.max_right(0, |c| c.0.len() <= 4), 4);
+        assert_eq!(st.min_left(s.len(), |c| c.0.len() <= 4), 7);
+    }
+
+    #[test]
+    fn range_gcd() {
+        fn gcd(a: i64, b: i64) -> i64 {
+            if b == 0 {
+                a.abs()
+            } else {
+                gcd(b, a % b)
+            }
+        }
+
+        let mut rng = XorShift::new(2_463_534_242);
+
+        let n = 20;
+        let mut naive: Vec<i64> = (0..n).map(|_| (rng.next_u64() % 12 * 6) as i64).collect();
+        let mut st = RangeGcd::from_raw(&naive);
+        assert_eq!(st.len(), n);
+        for _ in 0..500 {
+            let idx = (rng.next_u64() % n as u64) as usize;
+            let value = (rng.next_u64() % 10 * 6) as i64 - 30;
+            st.update_raw(idx, value);
+            naive[idx] = value;
+
+            let a = (rng.next_u64() % (n as u64 + 1)) as usize;
+            let b = (rng.next_u64() % (n as u64 + 1)) as usize;
+            let (l, r) = (a.min(b), a.max(b));
+            let expected = naive[l..r].iter().fold(0, |acc, &x| gcd(acc, x));
+            assert_eq!(st.gcd(l..r), expected);
+        }
+        assert_eq!(RangeGcd::from_raw(&[]).gcd(..), 0);
+    }
 }
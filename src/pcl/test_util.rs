@@ -0,0 +1,26 @@
+//! テストでのみ使う補助的な道具を定義する。
+
+/// テスト用の xorshift 疑似乱数生成器。
+///
+/// 乱数の質よりも、シードを固定すれば毎回同じ列が得られることを重視している。シードは 0 であって
+/// はならない。
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// 指定したシードで生成する。
+    pub fn new(seed: u64) -> XorShift {
+        assert_ne!(seed, 0, "seed must not be 0");
+        XorShift { state: seed }
+    }
+
+    /// 次の乱数を返す。
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
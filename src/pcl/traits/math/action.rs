@@ -0,0 +1,130 @@
+//! モノイド作用の定義といくつかの実装。
+
+use super::monoid::{Max, Min, Monoid};
+use crate::pcl::compat::num::{One, Zero};
+use crate::pcl::traits::utils::num::{MaxValue, MinValue};
+use std::fmt;
+use std::ops::{Add, Mul};
+
+/// モノイド作用
+///
+/// 作用素の集合 F がモノイド M に作用するとは、F 自身がモノイドであって、次の条件を満たす写像
+/// apply(F, M) -> M が定義されていることをいう。
+///
+/// - 単位元は何もしない: apply(id, x) = x 。
+/// - 合成と両立する: apply(op(f, g), x) = apply(g, apply(f, x)) 。
+/// - 準同型である: apply(f, op(x, y)) = op(apply(f, x), apply(f, y)) 。
+///
+/// すなわち作用素の演算 `op(f, g)` は「f を作用させてから g を作用させる」という合成を表す。遅延評
+/// 価セグメント木 (`LazySegmentTree`) と一緒に使う。
+pub trait Action<M: Monoid>: Monoid {
+    /// 作用
+    fn apply(f: Self, x: M) -> M;
+}
+
+/// モノイド作用の実装: アフィン変換 x -> mul * x + add
+///
+/// `Min` や `Max` に作用させる場合、大小関係を保つために `mul` は非負でなければならない。`mul` を
+/// 1 にすれば区間加算、0 にすれば区間代入になる。値が単位元 (`Min` なら最大値、`Max` なら最小値) で
+/// あっても特別扱いはしないので、加算であふれないよう注意すること。`LazySegmentTree` は配列の外側
+/// (長さを 2 冪に揃えるための余白) には作用させない。
+pub struct Affine<T> {
+    pub mul: T,
+    pub add: T,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Affine<T> {
+    fn fmt<'a>(&self, f: &mut fmt::Formatter<'a>) -> fmt::Result {
+        f.debug_struct("Affine")
+            .field("mul", &self.mul)
+            .field("add", &self.add)
+            .finish()
+    }
+}
+
+impl<T: Clone> Clone for Affine<T> {
+    fn clone(&self) -> Self {
+        Affine {
+            mul: self.mul.clone(),
+            add: self.add.clone(),
+        }
+    }
+}
+
+impl<T: Copy> Copy for Affine<T> {}
+
+impl<T: Zero + One> Affine<T> {
+    /// 各要素に `value` を加える作用素を生成する。
+    pub fn add(value: T) -> Affine<T> {
+        Affine {
+            mul: T::one(),
+            add: value,
+        }
+    }
+
+    /// 各要素を `value` に書き換える作用素を生成する。
+    pub fn assign(value: T) -> Affine<T> {
+        Affine {
+            mul: T::zero(),
+            add: value,
+        }
+    }
+}
+
+impl<T> Monoid for Affine<T>
+where
+    T: Zero + One + Add<Output = T> + Mul<Output = T> + Copy,
+{
+    fn op(f: Self, g: Self) -> Self {
+        // g(f(x)) = g.mul * (f.mul * x + f.add) + g.add
+        Affine {
+            mul: g.mul * f.mul,
+            add: g.mul * f.add + g.add,
+        }
+    }
+
+    fn id() -> Self {
+        Affine {
+            mul: T::one(),
+            add: T::zero(),
+        }
+    }
+}
+
+impl<T> Action<Min<T>> for Affine<T>
+where
+    T: Zero + One + Add<Output = T> + Mul<Output = T> + Copy + Ord + MaxValue,
+{
+    fn apply(f: Self, x: Min<T>) -> Min<T> {
+        Min(f.mul * x.0 + f.add)
+    }
+}
+
+impl<T> Action<Max<T>> for Affine<T>
+where
+    T: Zero + One + Add<Output = T> + Mul<Output = T> + Copy + Ord + MinValue,
+{
+    fn apply(f: Self, x: Max<T>) -> Max<T> {
+        Max(f.mul * x.0 + f.add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn affine() {
+        let f = Affine { mul: 2, add: 3 };
+        let g = Affine::add(-1);
+        assert_eq!(Affine::apply(f, Min(5)).0, 13);
+        assert_eq!(Affine::apply(Affine::op(f, g), Min(5)).0, 12);
+        assert_eq!(Affine::apply(Affine::op(g, f), Min(5)).0, 11);
+        assert_eq!(Affine::apply(Affine::op(f, Affine::assign(7)), Max(5)).0, 7);
+        assert_eq!(Affine::apply(Affine::<i32>::id(), Max(5)).0, 5);
+
+        // 単位元と同じ値であっても代入は行われる。
+        assert_eq!(Affine::apply(Affine::assign(1), Min::<i32>::id()).0, 1);
+        assert_eq!(Affine::apply(Affine::assign(-1), Max::<i32>::id()).0, -1);
+    }
+}
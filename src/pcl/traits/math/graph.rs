@@ -4,6 +4,8 @@ use crate::pcl::compat::num::One;
 use crate::{member_name_of, type_name_of};
 use std::fmt;
 use std::hash;
+use std::iter;
+use std::slice;
 
 /// グラフの辺を表す。
 pub struct Edge<C> {
@@ -124,7 +126,34 @@ pub trait Graph: ReadonlyGraph {
 /// 無向グラフであることを示す。
 pub trait Undirected: ReadonlyGraph {}
 
+/// 隣接する頂点の番号を順に返すイテレータ。`ProvideAdjacencies::neighbors()` の戻り値。
+pub type Neighbors<'a, C> = iter::Map<slice::Iter<'a, Edge<C>>, fn(&Edge<C>) -> usize>;
+
 /// ある頂点から隣接する頂点のリストを取得できることを示す。
 pub trait ProvideAdjacencies: ReadonlyGraph {
     fn get_adjacencies(&self, idx: usize) -> Option<&[Edge<Self::Cost>]>;
+
+    /// ある頂点から出る辺の行き先の頂点を順に返す。重みを気にしない探索などで使う。
+    ///
+    /// ```
+    /// # use procon_lib::pcl::structure::graph::AdjacencyList;
+    /// # use procon_lib::pcl::traits::math::graph::{Graph, ProvideAdjacencies};
+    /// // use crate::pcl::structure::graph::AdjacencyList;
+    /// // use crate::pcl::traits::math::graph::{Graph, ProvideAdjacencies};
+    /// let mut graph = AdjacencyList::<i64>::of_size(4);
+    /// graph.add_edges(vec![(0, 2, 5), (0, 1, 3), (1, 3, 1)]);
+    /// assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![2, 1]);
+    /// assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![3]);
+    /// assert_eq!(graph.neighbors(3).next(), None);
+    /// ```
+    fn neighbors(&self, idx: usize) -> Neighbors<'_, Self::Cost> {
+        fn destination<C>(edge: &Edge<C>) -> usize {
+            edge.to
+        }
+
+        self.get_adjacencies(idx)
+            .expect("vertex index out of bounds")
+            .iter()
+            .map(destination::<Self::Cost> as fn(&Edge<Self::Cost>) -> usize)
+    }
 }
@@ -3,10 +3,12 @@
 //! 例えば、累積和は通常の (ℤ,+) でなくても群であればよい。そのために `Group` というトレイトを準備し
 //! て群を定義する。
 
+pub mod action;
 pub mod graph;
 pub mod group;
 pub mod monoid;
 
-pub use self::graph::{Edge, Graph, ProvideAdjacencies, ReadonlyGraph, Undirected};
+pub use self::action::{Action, Affine};
+pub use self::graph::{Edge, Graph, Neighbors, ProvideAdjacencies, ReadonlyGraph, Undirected};
 pub use self::group::Group;
 pub use self::monoid::Monoid;
@@ -83,6 +83,76 @@ impl<T: Ord + MinValue> Monoid for Max<T> {
     }
 }
 
+/// モノイドの実装: 文字列の連結
+///
+/// 単位元は空文字列。 Segment Tree と一緒に使うと、区間に対応する部分文字列が得られる。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Concat(pub String);
+
+impl Monoid for Concat {
+    fn op(mut x: Self, y: Self) -> Self {
+        x.0.push_str(&y.0);
+        x
+    }
+
+    fn id() -> Self {
+        Concat(String::new())
+    }
+}
+
+/// モノイドの実装: 最大公約数を取る演算
+///
+/// 単位元は 0 。結果は常に非負になる。 Segment Tree と一緒に使うと区間の最大公約数が求まる。
+///
+/// 符号付き整数の場合は絶対値を対応する符号なし整数で計算するので、`i64::MIN` なども扱える。ただ
+/// し結果が `T` に収まらない場合 (`Gcd(i64::MIN)` と `Gcd(0)` の演算など) は panic する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gcd<T>(pub T);
+
+macro_rules! impl_gcd_monoid {
+    (unsigned $($ty:ty)*) => {
+        $(
+        impl Monoid for Gcd<$ty> {
+            fn op(x: Self, y: Self) -> Self {
+                let (mut a, mut b) = (x.0, y.0);
+                while b != 0 {
+                    let r = a % b;
+                    a = b;
+                    b = r;
+                }
+
+                Gcd(a)
+            }
+
+            fn id() -> Self {
+                Gcd(0)
+            }
+        }
+        )*
+    };
+    (signed $($ty:ty => $uty:ty)*) => {
+        $(
+        impl Monoid for Gcd<$ty> {
+            fn op(x: Self, y: Self) -> Self {
+                // abs() だと最小値であふれるので、符号なし整数にしてから符号を反転する。
+                let abs = |v: $ty| if v < 0 { (v as $uty).wrapping_neg() } else { v as $uty };
+                let g = Gcd::op(Gcd(abs(x.0)), Gcd(abs(y.0))).0 as $ty;
+                assert!(g >= 0, "gcd does not fit in {}", stringify!($ty));
+
+                Gcd(g)
+            }
+
+            fn id() -> Self {
+                Gcd(0)
+            }
+        }
+        )*
+    };
+}
+
+impl_gcd_monoid!(unsigned u8 u16 u32 u64 u128 usize);
+impl_gcd_monoid!(signed i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 isize => usize);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +170,32 @@ mod tests {
         assert_eq!(Max::<i32>::id().0, ::std::i32::MIN);
         assert_eq!(Max::op(Max(1), Max::id()).0, 1);
     }
+
+    #[test]
+    fn gcd() {
+        assert_eq!(Gcd::op(Gcd(12), Gcd(18)).0, 6);
+        assert_eq!(Gcd::op(Gcd(-12i64), Gcd(18)).0, 6);
+        assert_eq!(Gcd::op(Gcd(7u32), Gcd::id()).0, 7);
+        assert_eq!(Gcd::op(Gcd::id(), Gcd(-5i32)).0, 5);
+        assert_eq!(Gcd::<i64>::id().0, 0);
+        // 最小値の絶対値を取ってもあふれない。
+        assert_eq!(Gcd::op(Gcd(Max::<i64>::id().0), Gcd(6)).0, 2);
+        assert_eq!(Gcd::op(Gcd(-4i8), Gcd(Max::<i8>::id().0)).0, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn gcd_does_not_fit() {
+        // i64::MIN の絶対値は i64 に収まらない。
+        Gcd::op(Gcd(Max::<i64>::id().0), Gcd::id());
+    }
+
+    #[test]
+    fn concat() {
+        let x = Concat("ab".to_string());
+        let y = Concat("cde".to_string());
+        assert_eq!(Concat::op(x.clone(), y.clone()).0, "abcde");
+        assert_eq!(Concat::op(y, x.clone()).0, "cdeab");
+        assert_eq!(Concat::op(Concat::id(), x.clone()), x);
+    }
 }
@@ -16,6 +16,14 @@ pub trait MinValue {
     fn min_value() -> Self;
 }
 
+/// 値域の端で飽和する加算を定義する。
+///
+/// 最短路の計算などで大きなコストを足し合わせても、オーバーフローで panic したり値が回り込んだり
+/// しないようにするために使う。
+pub trait SaturatingAdd {
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
 macro_rules! impl_minmax_value_for_primitives {
     ($($ty:tt)*) => {
         $(
@@ -35,6 +43,23 @@ macro_rules! impl_minmax_value_for_primitives {
 }
 
 impl_minmax_value_for_primitives! {
-    u8 u16 u32 u64 usize
-    i8 i16 i32 i64 isize
+    u8 u16 u32 u64 u128 usize
+    i8 i16 i32 i64 i128 isize
+}
+
+macro_rules! impl_saturating_add_for_primitives {
+    ($($ty:tt)*) => {
+        $(
+        impl SaturatingAdd for $ty {
+            fn saturating_add(self, rhs: $ty) -> $ty {
+                $ty::saturating_add(self, rhs)
+            }
+        }
+        )*
+    };
+}
+
+impl_saturating_add_for_primitives! {
+    u8 u16 u32 u64 u128 usize
+    i8 i16 i32 i64 i128 isize
 }
@@ -0,0 +1,108 @@
+//! 再帰関数のメモ化を簡単にする `Memo` を定義する。
+//!
+//! 内部では `RefCell<HashMap<K, V>>` を保持しており、共有参照 `&Memo` だけで値を記録できる。そのた
+//! め計算用のクロージャに `&Memo` を渡して、その中から再帰的に `get_or_compute` を呼ぶことができる。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::utils::memo::Memo;
+//! // use crate::pcl::utils::memo::Memo;
+//! fn fib(memo: &Memo<u64, u64>, n: u64) -> u64 {
+//!     memo.get_or_compute(n, |memo| {
+//!         if n < 2 {
+//!             n
+//!         } else {
+//!             fib(memo, n - 1) + fib(memo, n - 2)
+//!         }
+//!     })
+//! }
+//!
+//! let memo = Memo::new();
+//! assert_eq!(fib(&memo, 90), 2_880_067_194_370_816_120);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// 計算済みの値を記録しておくメモ。
+pub struct Memo<K, V> {
+    table: RefCell<HashMap<K, V>>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Memo<K, V> {
+    /// 空のメモを生成する。
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            table: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// `key` に対する値が記録されていればそれを返し、なければ `f` で計算して記録してから返す。
+    ///
+    /// `f` にはこのメモ自身が渡されるので、その中で再帰的に `get_or_compute` を呼んでよい。`f` の実
+    /// 行中は内部の `HashMap` を借用していない。
+    pub fn get_or_compute<F: FnOnce(&Memo<K, V>) -> V>(&self, key: K, f: F) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = f(self);
+        self.table.borrow_mut().insert(key, value.clone());
+        value
+    }
+
+    /// 記録されている値を取得する。
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.table.borrow().get(key).cloned()
+    }
+
+    /// 記録されている値の個数を返す。
+    pub fn len(&self) -> usize {
+        self.table.borrow().len()
+    }
+
+    /// 一つも値が記録されていないかどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.table.borrow().is_empty()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn memo_fib() {
+        fn fib(memo: &Memo<u64, u64>, calls: &Cell<usize>, n: u64) -> u64 {
+            memo.get_or_compute(n, |memo| {
+                calls.set(calls.get() + 1);
+                if n < 2 {
+                    n
+                } else {
+                    fib(memo, calls, n - 1) + fib(memo, calls, n - 2)
+                }
+            })
+        }
+
+        let memo = Memo::new();
+        let calls = Cell::new(0);
+        assert_eq!(fib(&memo, &calls, 50), 12_586_269_025);
+        // 0..=50 のそれぞれについてちょうど一回ずつ計算される。
+        assert_eq!(calls.get(), 51);
+        assert_eq!(memo.len(), 51);
+
+        assert_eq!(fib(&memo, &calls, 40), 102_334_155);
+        assert_eq!(calls.get(), 51);
+        assert_eq!(memo.get(&10), Some(55));
+        assert_eq!(memo.get(&51), None);
+    }
+}
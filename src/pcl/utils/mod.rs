@@ -1,4 +1,8 @@
 //! ユーティリティを定義する。
 
 pub mod macros;
+pub mod memo;
 pub mod range;
+pub mod relabel;
+pub mod rotate;
+pub mod scan;
@@ -0,0 +1,63 @@
+//! 頂点の番号がまばらなグラフの辺を、`0..k` の番号に詰め直す `relabel_graph` を定義する。
+//!
+//! 頂点の番号が 10^9 程度まであるが実際に使われるのは辺に現れるものだけ、という入力では、まず番号
+//! を座標圧縮してから隣接リストなどを作る。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::utils::relabel::relabel_graph;
+//! // use crate::pcl::utils::relabel::relabel_graph;
+//! let (k, edges, labels) = relabel_graph(&[(100, -7), (-7, 1_000_000_000)]);
+//! assert_eq!(k, 3);
+//! assert_eq!(edges, vec![(1, 0), (0, 2)]);
+//! assert_eq!(labels, vec![-7, 100, 1_000_000_000]);
+//! ```
+
+/// 辺に現れる頂点の番号を、小さい順に `0..k` に振り直す。
+///
+/// 戻り値は (頂点数 `k`, 振り直した辺のリスト, 新しい番号から元の番号への対応表) 。新しい番号の大
+/// 小関係は元の番号の大小関係と一致する。
+///
+/// # 計算量
+///
+/// O(E log E)
+pub fn relabel_graph(edges: &[(i64, i64)]) -> (usize, Vec<(usize, usize)>, Vec<i64>) {
+    let mut labels = Vec::with_capacity(2 * edges.len());
+    for &(u, v) in edges {
+        labels.push(u);
+        labels.push(v);
+    }
+    labels.sort_unstable();
+    labels.dedup();
+
+    let index = |x: i64| labels.binary_search(&x).expect("label must exist");
+    let remapped = edges.iter().map(|&(u, v)| (index(u), index(v))).collect();
+
+    (labels.len(), remapped, labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relabel_sparse_labels() {
+        let edges = [
+            (1_000_000_000_000, 5),
+            (5, -3),
+            (-3, 1_000_000_000_000),
+            (42, 42),
+            (5, 1_000_000_000_000),
+        ];
+        let (k, remapped, labels) = relabel_graph(&edges);
+        assert_eq!(k, 4);
+        assert_eq!(labels, vec![-3, 5, 42, 1_000_000_000_000]);
+        assert_eq!(remapped, vec![(3, 1), (1, 0), (0, 3), (2, 2), (1, 3)]);
+        for (&(u, v), &(a, b)) in edges.iter().zip(&remapped) {
+            assert_eq!((labels[a], labels[b]), (u, v));
+        }
+
+        assert_eq!(relabel_graph(&[]), (0, vec![], vec![]));
+    }
+}
@@ -0,0 +1,71 @@
+//! スライスを巡回シフトした新しい `Vec` を返す `rotate_left_copy`, `rotate_right_copy` を定義す
+//! る。
+//!
+//! 標準ライブラリの `rotate_left` などはその場で並べ替えるうえ、シフト量が長さ以下でなければならな
+//! い。こちらはシフト量を長さで割った余りに正規化するので、巡回シフトの候補を列挙するときに便利。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::utils::rotate::{rotate_left_copy, rotate_right_copy};
+//! // use crate::pcl::utils::rotate::{rotate_left_copy, rotate_right_copy};
+//! assert_eq!(rotate_left_copy(&[1, 2, 3, 4], 1), vec![2, 3, 4, 1]);
+//! assert_eq!(rotate_right_copy(&[1, 2, 3, 4], 1), vec![4, 1, 2, 3]);
+//! ```
+
+/// `slice` を左に `k` だけ巡回シフトした `Vec` を返す。`k` は長さで割った余りに正規化される。
+///
+/// # 計算量
+///
+/// O(n)
+pub fn rotate_left_copy<T: Clone>(slice: &[T], k: usize) -> Vec<T> {
+    if slice.is_empty() {
+        return Vec::new();
+    }
+
+    let k = k % slice.len();
+    let mut res = Vec::with_capacity(slice.len());
+    res.extend_from_slice(&slice[k..]);
+    res.extend_from_slice(&slice[..k]);
+    res
+}
+
+/// `slice` を右に `k` だけ巡回シフトした `Vec` を返す。`k` は長さで割った余りに正規化される。
+///
+/// # 計算量
+///
+/// O(n)
+pub fn rotate_right_copy<T: Clone>(slice: &[T], k: usize) -> Vec<T> {
+    if slice.is_empty() {
+        return Vec::new();
+    }
+
+    let k = k % slice.len();
+    rotate_left_copy(slice, slice.len() - k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_copy() {
+        let v = [1, 2, 3, 4];
+        assert_eq!(rotate_left_copy(&v, 6), vec![3, 4, 1, 2]);
+        assert_eq!(rotate_right_copy(&v, 6), vec![3, 4, 1, 2]);
+        assert_eq!(rotate_left_copy(&v, 0), v.to_vec());
+        assert_eq!(rotate_right_copy(&v, 4), v.to_vec());
+        assert_eq!(rotate_right_copy(&v, 5), vec![4, 1, 2, 3]);
+
+        for k in 0..10 {
+            let mut expected = v.to_vec();
+            expected.rotate_left(k % v.len());
+            assert_eq!(rotate_left_copy(&v, k), expected);
+            assert_eq!(rotate_right_copy(&rotate_left_copy(&v, k), k), v.to_vec());
+        }
+
+        let empty: [String; 0] = [];
+        assert!(rotate_left_copy(&empty, 3).is_empty());
+        assert!(rotate_right_copy(&empty, 3).is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+//! 数列を左から畳み込みながら途中の状態をすべて記録する `scan_left` を定義する。
+//!
+//! # Examples
+//!
+//! ```
+//! # use procon_lib::pcl::utils::scan::scan_left;
+//! // use crate::pcl::utils::scan::scan_left;
+//! let prefix_max = scan_left(0, &[3, 1, 4, 1, 5], |&acc, &x| acc.max(x));
+//! assert_eq!(prefix_max, vec![0, 3, 3, 4, 4, 5]);
+//! ```
+
+/// `init` から始めて `slice` の各要素を順に `f` で畳み込み、途中の状態をすべて返す。
+///
+/// 戻り値の長さは `slice.len() + 1` で、最初の要素は `init` 、 `i + 1` 番目の要素は `slice[..=i]`
+/// までを畳み込んだ状態になる。 `Iterator::scan` と違い、初期状態も含めた状態の列がそのまま得られ
+/// る。
+///
+/// # 計算量
+///
+/// `f` の呼び出し n 回
+pub fn scan_left<T, A, F>(init: A, slice: &[T], mut f: F) -> Vec<A>
+where
+    T: Clone,
+    A: Clone,
+    F: FnMut(&A, &T) -> A,
+{
+    let mut states = Vec::with_capacity(slice.len() + 1);
+    states.push(init);
+    for x in slice {
+        let next = f(&states[states.len() - 1], x);
+        states.push(next);
+    }
+
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcl::math::CumSum;
+    use crate::pcl::traits::math::group::Additive as A;
+
+    #[test]
+    fn scan_left_prefix_sum() {
+        let values = [5, 4, 1, 3, 2, 6];
+        let states = scan_left(0, &values, |&acc, &x| acc + x);
+        assert_eq!(states.len(), values.len() + 1);
+
+        let cumsum = CumSum::from_array(values.iter().map(|&x| A(x)).collect::<Vec<_>>());
+        for (i, &s) in states.iter().enumerate() {
+            assert_eq!(s, cumsum.sum(..i).0);
+        }
+
+        assert_eq!(scan_left(7, &[] as &[i32], |&acc, &x| acc + x), vec![7]);
+    }
+
+    #[test]
+    fn scan_left_non_copy() {
+        let words = ["ab", "c", "de"];
+        let states = scan_left(String::new(), &words, |acc, &w| acc.clone() + w);
+        assert_eq!(states, vec!["", "ab", "abc", "abcde"]);
+    }
+}